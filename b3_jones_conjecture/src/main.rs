@@ -1,5 +1,21 @@
+#[cfg(test)]
+mod test;
+
+use std::collections::{BTreeMap, HashSet};
+use std::hash::Hash;
+
 extern crate poly;
+use poly::BigInt;
+use poly::CrtPoly;
+use poly::FracPoly;
+use poly::IntegerRing;
+use poly::Notation;
 use poly::Poly;
+use poly::PolyError;
+use poly::Ring;
+use poly::Root;
+use poly::Series;
+use poly::Zp;
 
 extern crate braid3;
 use braid3::Braid;
@@ -8,51 +24,84 @@ use braid3::Twist;
 // Data used by the dynamical programming algorithm to efficietly calculate the
 // Jones polynomial for descendant braids. For more info on how the algorithm
 // works and {A,B,C,D,E}-type joinings please read the pdf paper.
-struct BraidData {
+struct BraidData<R: Ring> {
   // The element of braid group for which data is stored.
   braid: Braid,
 
   // Kauffman bracket for the knot obtained by A-type joining of strands.
-  kauffman_a: Poly,
+  kauffman_a: Poly<R>,
 
   // Kauffman bracket for the knot obtained by B-type joining of strands.
-  kauffman_b: Poly,
+  kauffman_b: Poly<R>,
 
   // Kauffman bracket for the knot obtained by C-type joining of strands.
-  kauffman_c: Poly,
+  kauffman_c: Poly<R>,
 
   // Kauffman bracket for the knot obtained by D-type joining of strands.
-  kauffman_d: Poly,
+  kauffman_d: Poly<R>,
 
   // Kauffman bracket for the knot obtained by E-type joining of strands.
-  kauffman_e: Poly,
+  kauffman_e: Poly<R>,
 
   // Writhe of the corresponding knot (always A-type joining).
   writhe: i64,
 
   // Jones polynomial of the corresponding knot (always A-type joining).
-  jones: Poly,
+  jones: Poly<R>,
 }
 
-// Calculate Jones polynomial given the Kauffman bracket and the writhe number.
-fn calc_jones(kauffman: &Poly, writhe: i64) -> Poly {
-  let mut writhe_poly = Poly::zero();
-  let minus = if writhe % 2 == 0 { 1 } else { -1 };
-  writhe_poly.set_coef(-3 * writhe, minus);
-  kauffman * (&writhe_poly)
+// Calculate Jones polynomial given the Kauffman bracket and the writhe number:
+// the bracket times `(-A^-3)^writhe`. Like the bracket, it is a polynomial
+// in A.
+fn calc_jones<R: Ring>(kauffman: &Poly<R>, writhe: i64)
+    -> Result<Poly<R>, PolyError> {
+  let sign = if writhe % 2 == 0 { 1 } else { -1 };
+  let mut res = Poly::zero().with_var('A');
+  res.checked_add_scaled_shifted(kauffman, &R::from_i64(sign), -3 * writhe)?;
+  Ok(res)
 }
 
-// Kauffman bracket for the unknot: `-(t^{-2} + t^2)`.
-fn kauffman_unknot() -> Poly {
-    let mut res = Poly::zero();
-    res.set_coef(-2, -1);
-    res.set_coef(2,  -1);
-    res
+// Converts the Jones polynomial in A, as computed from the Kauffman bracket,
+// to the standard V(t) in t = A^-4. The bracket counts every loop as the
+// unknot bracket, so it is divided out once to normalize V(unknot) = 1. Links
+// with an even number of components get half-integer exponents.
+fn jones_in_t<R: Ring>(jones: &Poly<R>) -> Result<FracPoly<R>, PolyError> {
+  let normalized = jones.div_exact(&kauffman_unknot())?;
+  Ok(FracPoly::new(normalized.mirror().with_var('t'), 4))
 }
 
-impl BraidData {
+// Kauffman skein relation for a crossing of the given sign: the bracket is
+// `A^sign * x + A^-sign * y`, where `x` and `y` are the brackets of the two
+// smoothings of the crossing.
+fn smoothing<R: Ring>(x: &Poly<R>, y: &Poly<R>, sign: i64)
+    -> Result<Poly<R>, PolyError> {
+  let mut res = x.clone();
+  res.shift_exponents(sign);
+  res.checked_add_scaled_shifted(y, &R::one(), -sign)?;
+  Ok(res)
+}
+
+// Skein relation for a crossing whose second smoothing closes an extra loop,
+// so that `y` is `x` times the unknot bracket `-(A^-2 + A^2)`.
+fn smoothing_with_loop<R: Ring>(x: &Poly<R>, sign: i64)
+    -> Result<Poly<R>, PolyError> {
+  let minus_one = R::from_i64(-1);
+  let mut res = x.clone();
+  res.shift_exponents(sign);
+  res.checked_add_scaled_shifted(x, &minus_one, -sign - 2)?;
+  res.checked_add_scaled_shifted(x, &minus_one, -sign + 2)?;
+  Ok(res)
+}
+
+// Kauffman bracket for the unknot: `-(A^{-2} + A^2)`.
+fn kauffman_unknot<R: Ring>() -> Poly<R> {
+  let terms = vec![(-2, R::from_i64(-1)), (2, R::from_i64(-1))];
+  terms.into_iter().collect::<Poly<R>>().with_var('A')
+}
+
+impl<R: Ring> BraidData<R> {
   // Braid data for the identity element of the braid group.
-  fn identity_braid() -> BraidData {
+  fn identity_braid() -> BraidData<R> {
     let kauffman_unknot_1 = kauffman_unknot();
     
     // Kauffman bracket for unknot, squared.
@@ -81,41 +130,27 @@ impl BraidData {
       // E-type joining of three untwisted strands gives 1 unknots.
       kauffman_e: kauffman_unknot_1.clone(),
 
+      // Zero writhe, so the Jones polynomial is just the Kauffman bracket.
       writhe: 0,
-      jones: calc_jones(&kauffman_unknot_3, 0),
+      jones: kauffman_unknot_3,
     }
   }
 
   // Calculates BraidData for descendants of the element of the braid group.
-  fn descendants(&self) -> Vec<BraidData> {
+  // Fails if the coefficient ring `R` cannot represent some Kauffman bracket.
+  fn descendants(&self) -> Result<Vec<BraidData<R>>, PolyError> {
     let mut res = Vec::with_capacity(4);
     for braid in self.braid.descendants().into_iter() {
       match braid.last_twist().unwrap() {
         Twist::A => {
-          let kauffman_a =
-            &(&Poly::identity() * &self.kauffman_a) +
-            &(&Poly::inverse_identity() * &self.kauffman_b);
-
-          let kauffman_b =
-            &(&Poly::identity() * &self.kauffman_b) +
-            &(&(&Poly::inverse_identity() * &self.kauffman_b) *
-              &kauffman_unknot());
-          
-          let kauffman_c =
-            &(&Poly::identity() * &self.kauffman_c) +
-            &(&Poly::inverse_identity() * &self.kauffman_d);
-
-          let kauffman_d =
-            &(&Poly::identity() * &self.kauffman_d) +
-            &(&(&Poly::inverse_identity() * &self.kauffman_d) *
-              &kauffman_unknot());
-          
-          let kauffman_e =
-            &(&Poly::identity() * &self.kauffman_e) +
-            &(&Poly::inverse_identity() * &self.kauffman_b);
+          let kauffman_a = smoothing(&self.kauffman_a, &self.kauffman_b, 1)?;
+          let kauffman_b = smoothing_with_loop(&self.kauffman_b, 1)?;
+          let kauffman_c = smoothing(&self.kauffman_c, &self.kauffman_d, 1)?;
+          let kauffman_d = smoothing_with_loop(&self.kauffman_d, 1)?;
+          let kauffman_e = smoothing(&self.kauffman_e, &self.kauffman_b, 1)?;
 
           let writhe = self.writhe + 1;
-          let jones = calc_jones(&kauffman_a, writhe);
+          let jones = calc_jones(&kauffman_a, writhe)?;
 
           res.push(BraidData {
             braid,
@@ -130,30 +165,14 @@ impl BraidData {
         },
  
         Twist::B => {
-          let kauffman_a =
-            &(&Poly::identity() * &self.kauffman_a) +
-            &(&Poly::inverse_identity() * &self.kauffman_c);
-
-          let kauffman_b =
-            &(&Poly::identity() * &self.kauffman_b) +
-            &(&Poly::inverse_identity() * &self.kauffman_e);
-          
-          let kauffman_c =
-            &(&Poly::identity() * &self.kauffman_c) +
-            &(&(&Poly::inverse_identity() * &self.kauffman_c) *
-              &kauffman_unknot());
-
-          let kauffman_d =
-            &(&Poly::identity() * &self.kauffman_d) +
-            &(&Poly::inverse_identity() * &self.kauffman_c);
-          
-          let kauffman_e =
-            &(&Poly::identity() * &self.kauffman_e) +
-            &(&(&Poly::inverse_identity() * &self.kauffman_e) *
-              &kauffman_unknot());
+          let kauffman_a = smoothing(&self.kauffman_a, &self.kauffman_c, 1)?;
+          let kauffman_b = smoothing(&self.kauffman_b, &self.kauffman_e, 1)?;
+          let kauffman_c = smoothing_with_loop(&self.kauffman_c, 1)?;
+          let kauffman_d = smoothing(&self.kauffman_d, &self.kauffman_c, 1)?;
+          let kauffman_e = smoothing_with_loop(&self.kauffman_e, 1)?;
 
           let writhe = self.writhe + 1;
-          let jones = calc_jones(&kauffman_a, writhe);
+          let jones = calc_jones(&kauffman_a, writhe)?;
 
           res.push(BraidData {
             braid,
@@ -168,30 +187,14 @@ impl BraidData {
         },
         
         Twist::Ainv => {
-          let kauffman_a =
-            &(&Poly::inverse_identity() * &self.kauffman_a) +
-            &(&Poly::identity() * &self.kauffman_b);
-
-          let kauffman_b =
-            &(&Poly::inverse_identity() * &self.kauffman_b) +
-            &(&(&Poly::identity() * &self.kauffman_b) *
-              &kauffman_unknot());
-          
-          let kauffman_c =
-            &(&Poly::inverse_identity() * &self.kauffman_c) +
-            &(&Poly::identity() * &self.kauffman_d);
-
-          let kauffman_d =
-            &(&Poly::inverse_identity() * &self.kauffman_d) +
-            &(&(&Poly::identity() * &self.kauffman_d) *
-              &kauffman_unknot());
-          
-          let kauffman_e =
-            &(&Poly::inverse_identity() * &self.kauffman_e) +
-            &(&Poly::identity() * &self.kauffman_b);
+          let kauffman_a = smoothing(&self.kauffman_a, &self.kauffman_b, -1)?;
+          let kauffman_b = smoothing_with_loop(&self.kauffman_b, -1)?;
+          let kauffman_c = smoothing(&self.kauffman_c, &self.kauffman_d, -1)?;
+          let kauffman_d = smoothing_with_loop(&self.kauffman_d, -1)?;
+          let kauffman_e = smoothing(&self.kauffman_e, &self.kauffman_b, -1)?;
 
           let writhe = self.writhe - 1;
-          let jones = calc_jones(&kauffman_a, writhe);
+          let jones = calc_jones(&kauffman_a, writhe)?;
 
           res.push(BraidData {
             braid,
@@ -206,30 +209,14 @@ impl BraidData {
         },
         
         Twist::Binv => {
-          let kauffman_a =
-            &(&Poly::inverse_identity() * &self.kauffman_a) +
-            &(&Poly::identity() * &self.kauffman_c);
-
-          let kauffman_b =
-            &(&Poly::inverse_identity() * &self.kauffman_b) +
-            &(&Poly::identity() * &self.kauffman_e);
-          
-          let kauffman_c =
-            &(&Poly::inverse_identity() * &self.kauffman_c) +
-            &(&(&Poly::identity() * &self.kauffman_c) *
-              &kauffman_unknot());
-
-          let kauffman_d =
-            &(&Poly::inverse_identity() * &self.kauffman_d) +
-            &(&Poly::identity() * &self.kauffman_c);
-          
-          let kauffman_e =
-            &(&Poly::inverse_identity() * &self.kauffman_e) +
-            &(&(&Poly::identity() * &self.kauffman_e) *
-              &kauffman_unknot());
+          let kauffman_a = smoothing(&self.kauffman_a, &self.kauffman_c, -1)?;
+          let kauffman_b = smoothing(&self.kauffman_b, &self.kauffman_e, -1)?;
+          let kauffman_c = smoothing_with_loop(&self.kauffman_c, -1)?;
+          let kauffman_d = smoothing(&self.kauffman_d, &self.kauffman_c, -1)?;
+          let kauffman_e = smoothing_with_loop(&self.kauffman_e, -1)?;
 
           let writhe = self.writhe - 1;
-          let jones = calc_jones(&kauffman_a, writhe);
+          let jones = calc_jones(&kauffman_a, writhe)?;
 
           res.push(BraidData {
             braid,
@@ -245,18 +232,18 @@ impl BraidData {
       }
     }
     res.shrink_to_fit();
-    res
+    Ok(res)
   }
 }
 
 // Reduced BraidData (with only the braid and its Jones polynomial left).
-struct BraidJones {
+struct BraidJones<R: Ring> {
   braid: Braid,
-  jones: Poly,
+  jones: Poly<R>,
 }
 
-impl BraidJones {
-  fn clone_from(data: &BraidData) -> BraidJones {
+impl<R: Ring> BraidJones<R> {
+  fn clone_from(data: &BraidData<R>) -> BraidJones<R> {
     BraidJones {
       braid: data.braid.clone(),
       jones: data.jones.clone(),
@@ -264,35 +251,84 @@ impl BraidJones {
   }
 }
 
+// Jones polynomials of all braids up to some length, and the full data for
+// the longest braids (needed to compute their descendants).
+type BraidJonesTable<R> = (Vec<BraidJones<R>>, Vec<BraidData<R>>);
+
 // Recursive function. Accepts the maximal canonical length of the braid,
 // returns a pair of (bj, bd), where:
 // * bj is a list of all BraidJones structures for all braids with canonical
 //   length <= n.
 // * bd is a list of all BraidData structures for all braids with canonical
 //   length of exactly n.
-fn calc_braid_jones_rec(n: u32) -> (Vec<BraidJones>, Vec<BraidData>) {
+// Fails if the coefficient ring `R` overflows on some braid.
+fn calc_braid_jones_rec<R: Ring>(n: u32)
+    -> Result<BraidJonesTable<R>, PolyError> {
   if n == 0 {
     let bdata = BraidData::identity_braid();
     let bjones = BraidJones::clone_from(&bdata);
-    return (vec![bjones], vec![bdata]);
+    return Ok((vec![bjones], vec![bdata]));
   }
 
-  let (mut bjones, bdata) = calc_braid_jones_rec(n - 1);
+  let (mut bjones, bdata) = calc_braid_jones_rec(n - 1)?;
   let mut new_bdata = Vec::with_capacity(bdata.len() * 3);
   for i in bdata.into_iter() {
-    for d in i.descendants().into_iter() {
+    for d in i.descendants()?.into_iter() {
       bjones.push(BraidJones::clone_from(&d));
       new_bdata.push(d);
     }
   }
   new_bdata.shrink_to_fit();
 
-  (bjones, new_bdata)
+  Ok((bjones, new_bdata))
 }
 
 // Wrapper around `calc_braid_jones_rec`. Only returns the BraidJones objects.
-fn calc_braid_jones(n: u32) -> Vec<BraidJones> {
-  let (res, _) = calc_braid_jones_rec(n);
+fn calc_braid_jones<R: Ring>(n: u32) -> Result<Vec<BraidJones<R>>, PolyError> {
+  let (res, _) = calc_braid_jones_rec(n)?;
+  Ok(res)
+}
+
+// Word-size primes for the multi-modular computation: the largest ones below
+// 2^62.
+const PRIME_1: u64 = 4611686018427387847;
+const PRIME_2: u64 = 4611686018427387817;
+const PRIME_3: u64 = 4611686018427387787;
+const PRIME_4: u64 = 4611686018427387761;
+
+// Runs the DP with coefficients modulo `P`, and adds the resulting images of
+// the Jones polynomials to their reconstructions, in the same order, starting
+// them on the first call. Gives the braids in that order.
+fn add_modular_images<const P: u64>(n: u32, crt: &mut Vec<CrtPoly>)
+    -> Vec<Braid> {
+  let bj = calc_braid_jones::<Zp<P>>(n)
+    .expect("modular arithmetic never overflows");
+  crt.resize(bj.len(), CrtPoly::new());
+  crt.iter_mut().zip(bj)
+    .map(|(c, i)| {
+      c.add_image(&i.jones);
+      i.braid
+    })
+    .collect()
+}
+
+// Calculates the Jones polynomials modulo several primes and reconstructs the
+// exact integer coefficients. Three primes cover coefficients up to 2^185;
+// the fourth one checks that the reconstruction does not change any more.
+fn calc_braid_jones_modular(n: u32) -> Vec<BraidJones<BigInt>> {
+  let mut crt = Vec::new();
+  let braids = add_modular_images::<PRIME_1>(n, &mut crt);
+  add_modular_images::<PRIME_2>(n, &mut crt);
+  add_modular_images::<PRIME_3>(n, &mut crt);
+  let partial: Vec<Poly<BigInt>> = crt.iter().map(CrtPoly::result).collect();
+  add_modular_images::<PRIME_4>(n, &mut crt);
+  let res: Vec<BraidJones<BigInt>> = braids.into_iter().zip(crt.iter())
+    .map(|(braid, c)| BraidJones { braid, jones: c.result() })
+    .collect();
+  if res.iter().zip(partial.iter()).any(|(i, p)| i.jones != *p) {
+    eprintln!("Warning: coefficients are too large for the moduli, the \
+               results may be wrong");
+  }
   res
 }
 
@@ -330,17 +366,21 @@ pub fn present_braid(braid: &Braid) {
   }
 }
 
-fn main() {
-  let n = 4;  // Upper limit on canonical braid length.
-  let bj = calc_braid_jones(n);
+// Prints every braid with its Jones polynomial, followed by the statistics
+// on the conjecture.
+fn report_conjecture<R: Ring>(bj: &[BraidJones<R>]) {
   let mut last_zero_index_change_braid_len = -1_i64;
   for i in bj.iter() {
     present_braid(&i.braid);
     println!("Jones: {}", &i.jones.to_string());
-    println!("");
-    println!("");
-    println!("");
-    if i.jones.get_coef(0) != 0 {
+    let degrees = (i.jones.min_degree(), i.jones.max_degree());
+    if let (Some(min), Some(max)) = degrees {
+      println!("Degrees: from {} to {}, span {}", min, max, max - min);
+    }
+    println!();
+    println!();
+    println!();
+    if !i.jones.get_coef(0).is_zero() {
       last_zero_index_change_braid_len = i.braid.canonical_len() as i64;
     }
   }
@@ -349,3 +389,214 @@ fn main() {
            last_zero_index_change_braid_len);
   // Seems to be unbounded, grows with `n` :(
 }
+
+// Groups braids by their Jones polynomial. Prints the number of distinct
+// polynomials per braid length, the braids sharing a polynomial, and the
+// Jones-trivial braids (those with the same polynomial as the identity).
+fn report_groups<R: Ring + Hash + Ord>(bj: &[BraidJones<R>]) {
+  let mut by_len: BTreeMap<usize, (usize, HashSet<&Poly<R>>)> =
+    BTreeMap::new();
+  let mut groups: BTreeMap<&Poly<R>, Vec<&Braid>> = BTreeMap::new();
+  for i in bj.iter() {
+    let len_stats = by_len.entry(i.braid.canonical_len())
+      .or_insert_with(|| (0, HashSet::new()));
+    len_stats.0 += 1;
+    len_stats.1.insert(&i.jones);
+    groups.entry(&i.jones).or_default().push(&i.braid);
+  }
+
+  for (len, &(total, ref distinct)) in by_len.iter() {
+    println!("Length {}: {} braids, {} distinct Jones polynomials",
+             len, total, distinct.len());
+  }
+  println!("Distinct Jones polynomials: {}", groups.len());
+  println!();
+
+  println!("Braids sharing a Jones polynomial:");
+  for (jones, braids) in groups.iter() {
+    if braids.len() < 2 {
+      continue;
+    }
+    println!("Jones: {}", jones);
+    for braid in braids.iter() {
+      println!("  {:?}", braid.twists);
+    }
+  }
+  println!();
+
+  let identity_jones = &bj[0].jones;
+  println!("Jones-trivial braids:");
+  for braid in groups[identity_jones].iter() {
+    if braid.canonical_len() > 0 {
+      println!("  {:?}", braid.twists);
+    }
+  }
+}
+
+// Prints every braid with the Jones polynomial V(t) of its closure, in the
+// notation of the KnotInfo and LinkInfo databases.
+fn report_jones<R: Ring>(bj: &[BraidJones<R>]) -> Result<(), PolyError> {
+  for i in bj.iter() {
+    let jones = jones_in_t(&i.jones)?;
+    println!("{:?}: {}", i.braid.twists, jones.display(Notation::KnotInfo));
+  }
+  Ok(())
+}
+
+// The Jones polynomial V(t) of a closure divided by its lowest power of t. The
+// exponents of V(t) are all congruent modulo the denominator, so that power
+// may be fractional, but the quotient is a polynomial in t.
+fn jones_normalized<R: Ring>(jones: &FracPoly<R>)
+    -> Result<Poly<R>, PolyError> {
+  let mut numer = jones.numer_poly().clone();
+  numer.shift_exponents(-numer.min_degree().unwrap_or(0));
+  numer.substitute_fractional_power(1, jones.denom())
+}
+
+// Nonzero roots in t of the Jones polynomial V(t) of a closure, which are the
+// ones of `jones_normalized`.
+fn jones_roots<R: IntegerRing>(jones: &FracPoly<R>)
+    -> Result<Vec<Root>, PolyError> {
+  Ok(jones_normalized(jones)?.roots())
+}
+
+// Braid data for the braid with the given twists, found by walking the
+// descendants from the identity braid. Gives `None` unless the twists are in
+// canonical form.
+fn braid_data<R: Ring>(twists: &[Twist])
+    -> Result<Option<BraidData<R>>, PolyError> {
+  let mut data = BraidData::identity_braid();
+  for twist in twists.iter() {
+    let next = data.descendants()?.into_iter()
+      .find(|d| d.braid.last_twist() == Some(*twist));
+    match next {
+      Some(d) => data = d,
+      None => return Ok(None),
+    }
+  }
+  Ok(Some(data))
+}
+
+// Prints how the Jones polynomials of the closures of A^k B, which are the
+// torus links T(2, k), stabilize as k grows: the polynomial for every k,
+// divided by its lowest power of t, as a power series truncated at the first
+// coefficient where it differs from the one for k - 2.
+fn report_tails<R: Ring>(n: u32) -> Result<(), PolyError> {
+  let mut normalized: Vec<Poly<R>> = Vec::new();
+  for k in 1..n as usize {
+    let mut twists = vec![Twist::A; k];
+    twists.push(Twist::B);
+    let data = braid_data::<R>(&twists)?
+      .expect("A^k B is in canonical form");
+    let jones = jones_normalized(&jones_in_t(&data.jones)?)?;
+    if k >= 3 {
+      let prev = &normalized[k - 3];
+      let len = jones.max_degree().max(prev.max_degree()).unwrap_or(0) + 1;
+      let stable = (0..len).find(|&e| jones.get_coef(e) != prev.get_coef(e))
+        .unwrap_or(len);
+      println!("k = {}: {}", k, Series::from_poly(&jones, stable));
+    }
+    normalized.push(jones);
+  }
+  Ok(())
+}
+
+// Prints the root clouds of the distinct Jones polynomials V(t), in a format
+// ready for plotting: a comment line with the first braid having the
+// polynomial, followed by its roots in t as `re im error` lines.
+fn report_roots<R: IntegerRing + Hash>(bj: &[BraidJones<R>])
+    -> Result<(), PolyError> {
+  let mut seen = HashSet::new();
+  for i in bj.iter() {
+    if !seen.insert(&i.jones) {
+      continue;
+    }
+    let jones = jones_in_t(&i.jones)?;
+    println!("# {:?}: {}", i.braid.twists, jones.display(Notation::KnotInfo));
+    for root in jones_roots(&jones)?.iter() {
+      println!("{} {} {}", root.value.re, root.value.im, root.error);
+    }
+  }
+  Ok(())
+}
+
+// Prints the amphichirality candidates: the braids whose closures have Jones
+// polynomials with V(t) = V(t^-1), as amphichiral links must. Polynomials
+// equal to their mirror only up to another unit are just counted.
+fn report_amphichiral<R: Ring>(bj: &[BraidJones<R>]) -> Result<(), PolyError> {
+  let mut candidates = 0;
+  let mut up_to_unit = 0;
+  for i in bj.iter() {
+    match i.jones.mirror_unit() {
+      Some((ref sign, 0)) if sign.is_one() => {
+        candidates += 1;
+        let jones = jones_in_t(&i.jones)?;
+        println!("{:?}: {}", i.braid.twists, jones.display(Notation::KnotInfo));
+      },
+      Some(_) => up_to_unit += 1,
+      None => (),
+    }
+  }
+  println!("Amphichirality candidates: {} of {} braids", candidates, bj.len());
+  println!("Symmetric up to a nontrivial unit: {}", up_to_unit);
+  Ok(())
+}
+
+// Orders n of the roots of unity t = e^(2 pi i / n) in the `unity` mode.
+const UNITY_ORDERS: [u64; 5] = [2, 3, 4, 5, 6];
+
+// Prints every braid with the exact values of the Jones polynomial V(t) of its
+// closure at the roots of unity t = e^(2 pi i / n). A value is a polynomial in
+// z = e^(2 pi i / m), where m is n times the denominator of the exponents.
+fn report_unity<R: IntegerRing>(bj: &[BraidJones<R>])
+    -> Result<(), PolyError> {
+  for i in bj.iter() {
+    let jones = jones_in_t(&i.jones)?;
+    println!("{:?}: {}", i.braid.twists, jones.display(Notation::KnotInfo));
+    for &n in UNITY_ORDERS.iter() {
+      let value = jones.eval_at_root_of_unity(n, 1);
+      println!("  t = e^(2 pi i/{}): {}, z = e^(2 pi i/{})",
+               n, value, value.order());
+    }
+  }
+  Ok(())
+}
+
+// Runs the report selected by `mode` on the braids up to length `n`.
+fn run<R: IntegerRing + Hash>(mode: &str, n: u32) -> Result<(), PolyError> {
+  if mode == "tails" {
+    return report_tails::<R>(n);
+  }
+  let bj = calc_braid_jones::<R>(n)?;
+  match mode {
+    "conjecture" => report_conjecture(&bj),
+    "groups" => report_groups(&bj),
+    "jones" => report_jones(&bj)?,
+    "roots" => report_roots(&bj)?,
+    "unity" => report_unity(&bj)?,
+    "amphichiral" => report_amphichiral(&bj)?,
+    _ => panic!("Unknown mode: {}", mode),
+  }
+  Ok(())
+}
+
+// Usage: b3_jones_conjecture [MODE [N]], where MODE is one of `conjecture`
+// (default), `groups`, `jones`, `roots`, `unity`, `amphichiral`, `tails` and
+// `modular`, and N is the upper limit on canonical braid length. The `modular`
+// mode prints the same report as `conjecture`, computed modulo several primes
+// instead of with overflow checks.
+fn main() {
+  let args: Vec<String> = std::env::args().collect();
+  let mode = args.get(1).map_or("conjecture", String::as_str);
+  let n = args.get(2).map_or(4, |s| s.parse().expect("N must be a number"));
+  if mode == "modular" {
+    report_conjecture(&calc_braid_jones_modular(n));
+    return;
+  }
+  // Machine integers are fast but may overflow for long braids, in which
+  // case we start over with exact coefficients.
+  if let Err(err) = run::<i64>(mode, n) {
+    println!("Switching to big integers: {}", err);
+    run::<BigInt>(mode, n).unwrap();
+  }
+}
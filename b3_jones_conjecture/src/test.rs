@@ -0,0 +1,124 @@
+use braid3::Twist;
+use poly::{BigInt, FracPoly, Poly, Rational};
+
+use super::{braid_data, calc_braid_jones, calc_braid_jones_modular, jones_in_t,
+            jones_normalized, jones_roots};
+
+// Jones polynomial V(t) of the closure of the braid with the given twists.
+fn closure_jones(twists: &[Twist]) -> FracPoly {
+  let data = braid_data::<i64>(twists).unwrap()
+    .expect("braid is not in canonical form");
+  jones_in_t(&data.jones).unwrap()
+}
+
+// The polynomial given by the `coefs` in front of t^(min_num / denom),
+// t^((min_num + 1) / denom) and so on.
+fn frac_poly(min_num: i64, denom: i64, coefs: Vec<i64>) -> FracPoly {
+  FracPoly::new(Poly::from_coefs(min_num, coefs), denom)
+}
+
+#[test]
+fn unlinks() {
+  use braid3::Twist::*;
+  assert_eq!(closure_jones(&[A, B]), FracPoly::from(Poly::number(1)));
+  // Two and three unknots.
+  assert_eq!(closure_jones(&[Ainv]), frac_poly(-1, 2, vec![-1, 0, -1]));
+  assert_eq!(closure_jones(&[]), frac_poly(-1, 1, vec![1, 2, 1]));
+}
+
+#[test]
+fn knots() {
+  use braid3::Twist::*;
+  // Right-handed trefoil 3_1 and its mirror image.
+  assert_eq!(closure_jones(&[A, A, A, B]),
+             frac_poly(1, 1, vec![1, 0, 1, -1]));
+  assert_eq!(closure_jones(&[Ainv, Ainv, Ainv, Binv]),
+             frac_poly(-4, 1, vec![-1, 1, 0, 1]));
+  // Figure-eight knot 4_1.
+  assert_eq!(closure_jones(&[A, Binv, A, Binv]),
+             frac_poly(-2, 1, vec![1, -1, 1, -1, 1]));
+}
+
+#[test]
+fn links() {
+  use braid3::Twist::*;
+  // Hopf link L2a1 with positive linking number.
+  let hopf = closure_jones(&[A, A, B]);
+  assert_eq!(hopf, frac_poly(1, 2, vec![-1, 0, 0, 0, -1]));
+  assert_eq!(hopf.to_string(), "P(t) = -1 * t^(1/2)  +  -1 * t^(5/2)");
+  // Torus link T(2, 4), which is L4a1.
+  assert_eq!(closure_jones(&[A, A, A, A, B]),
+             frac_poly(3, 2, vec![-1, 0, 0, 0, -1, 0, 1, 0, -1]));
+  // Trefoil plus a separate unknot.
+  assert_eq!(closure_jones(&[A, A, A]),
+             frac_poly(1, 2, vec![-1, 0, -1, 0, -1, 0, 0, 0, 1]));
+}
+
+#[test]
+fn modular_matches_exact() {
+  let exact = calc_braid_jones::<BigInt>(6).unwrap();
+  let modular = calc_braid_jones_modular(6);
+  assert_eq!(modular.len(), exact.len());
+  for (m, e) in modular.iter().zip(exact.iter()) {
+    assert_eq!(m.braid, e.braid);
+    assert_eq!(m.jones, e.jones);
+  }
+}
+
+#[test]
+fn roots() {
+  use braid3::Twist::*;
+  // The Hopf link has V(t) = -t^(1/2) (1 + t^2), with roots i and -i.
+  let mut hopf: Vec<(i64, i64)> = jones_roots(&closure_jones(&[A, A, B]))
+    .unwrap().iter()
+    .map(|root| {
+      assert!(root.error < 1e-12);
+      (root.value.re.round() as i64, root.value.im.round() as i64)
+    })
+    .collect();
+  hopf.sort();
+  assert_eq!(hopf, vec![(0, -1), (0, 1)]);
+  // The figure-eight knot has V(t) = t^-2 - t^-1 + 1 - t + t^2.
+  assert_eq!(jones_roots(&closure_jones(&[A, Binv, A, Binv])).unwrap().len(),
+             4);
+}
+
+#[test]
+fn roots_of_unity() {
+  use braid3::Twist::*;
+  let trefoil = closure_jones(&[A, A, A, B]);
+  let figure_eight = closure_jones(&[A, Binv, A, Binv]);
+  // V(e^(2 pi i / 3)) = 1 for every knot, and |V(-1)| is the determinant.
+  for knot in [&trefoil, &figure_eight].iter() {
+    assert_eq!(knot.eval_at_root_of_unity(3, 1).to_rational(),
+               Some(Rational::from(1)));
+  }
+  assert_eq!(trefoil.eval_at_root_of_unity(2, 1).to_rational(),
+             Some(Rational::from(-3)));
+  assert_eq!(figure_eight.eval_at_root_of_unity(2, 1).to_rational(),
+             Some(Rational::from(5)));
+}
+
+#[test]
+fn amphichirality() {
+  use braid3::Twist::*;
+  // The figure-eight knot is amphichiral, the trefoil is not.
+  let figure_eight = closure_jones(&[A, Binv, A, Binv]);
+  assert_eq!(figure_eight.numer_poly().mirror_unit(), Some((1, 0)));
+  let trefoil = closure_jones(&[A, A, A, B]);
+  assert_eq!(trefoil.numer_poly().mirror_unit(), None);
+  // The Hopf link is only symmetric around t^(3/2).
+  let hopf = closure_jones(&[A, A, B]);
+  assert_eq!(hopf.numer_poly().mirror_unit(), Some((1, 6)));
+}
+
+#[test]
+fn normalized_jones() {
+  use braid3::Twist::*;
+  let hopf = closure_jones(&[A, A, B]);
+  assert_eq!(jones_normalized(&hopf), Ok(Poly::from_coefs(0, vec![-1, 0, -1])));
+  let trefoil = closure_jones(&[A, A, A, B]);
+  assert_eq!(jones_normalized(&trefoil),
+             Ok(Poly::from_coefs(0, vec![1, 0, 1, -1])));
+  assert!(braid_data::<i64>(&[A, Ainv]).unwrap().is_none());
+}
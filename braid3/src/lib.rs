@@ -30,7 +30,7 @@ impl Braid {
     self.twists.last().cloned()
   }
 
-  fn ends_with(&self, ending: &Vec<Twist>) -> bool {
+  fn ends_with(&self, ending: &[Twist]) -> bool {
     let n = self.twists.len();
     let m = ending.len();
     if n < m {
@@ -51,8 +51,8 @@ impl Braid {
   /// * `B A B`, `Binv Ainv Binv`, because they can be reduced to `A B A` and
   ///   `Ainv Binv Ainv` respectively.
   /// * `Binv A B A`, `A B A Binv`, `B Ainv Binv Ainv`, `Ainv Binv Ainb B`,
-  ///    because they can be reduced to `A B`, `B A`, Ainv Binv`, `Binv Ainv`
-  ///    respectively.
+  ///   because they can be reduced to `A B`, `B A`, Ainv Binv`, `Binv Ainv`
+  ///   respectively.
   pub fn descendants(&self) -> Vec<Braid> {
     let mut res = Vec::<Braid>::with_capacity(4);
     let n = self.twists.len();
@@ -64,8 +64,8 @@ impl Braid {
     // We can add `A` to the braid unless it ends with:
     // * `Ainv`, in which case `Ainv A` == `1`.
     // * `Binv A B`, in which case `Binv A B A` == `Binv B A B` == `A B`.
-    if !self.ends_with(&vec![Twist::Ainv]) &&
-       !self.ends_with(&vec![Twist::Binv, Twist::A, Twist::B]) {
+    if !self.ends_with(&[Twist::Ainv]) &&
+       !self.ends_with(&[Twist::Binv, Twist::A, Twist::B]) {
       new_twists.push(Twist::A);
       res.push(Braid { twists: new_twists.clone() });
       new_twists.pop();
@@ -75,9 +75,9 @@ impl Braid {
     // * `Binv`, in which case `Binv B` == `1`.
     // * `B A`, in which case `B A B` == `A B A`, and we allow the 2nd form.
     // * `Ainv Binv Ainv`, in which case `Ainv Binv Ainv B` == `Binv Ainv`.
-    if !self.ends_with(&vec![Twist::Binv]) &&
-       !self.ends_with(&vec![Twist::B, Twist::A]) &&
-       !self.ends_with(&vec![Twist::Ainv, Twist::Binv, Twist::Ainv]) {
+    if !self.ends_with(&[Twist::Binv]) &&
+       !self.ends_with(&[Twist::B, Twist::A]) &&
+       !self.ends_with(&[Twist::Ainv, Twist::Binv, Twist::Ainv]) {
       new_twists.push(Twist::B);
       res.push(Braid { twists: new_twists.clone() });
       new_twists.pop();
@@ -87,8 +87,8 @@ impl Braid {
     // * `A`, in which case `A Ainv` == `1`.
     // * `B Ainv Binv`, in which case `B Ainv Binv Ainv` == `B Binv Ainv Binv`
     //     == `Ainv Binv`.
-    if !self.ends_with(&vec![Twist::A]) &&
-       !self.ends_with(&vec![Twist::B, Twist::Ainv, Twist::Binv]) {
+    if !self.ends_with(&[Twist::A]) &&
+       !self.ends_with(&[Twist::B, Twist::Ainv, Twist::Binv]) {
       new_twists.push(Twist::Ainv);
       res.push(Braid { twists: new_twists.clone() });
       new_twists.pop();
@@ -99,9 +99,9 @@ impl Braid {
     // * `Binv Ainv`, in which case `Binv Ainv Binv` == `Ainv Binv Ainv`,
     //     and we allow the 2nd form.
     // * `A B A`, in which case `A B A Binv` == `B A`.
-    if !self.ends_with(&vec![Twist::B]) &&
-       !self.ends_with(&vec![Twist::Binv, Twist::Ainv]) &&
-       !self.ends_with(&vec![Twist::A, Twist::B, Twist::A]) {
+    if !self.ends_with(&[Twist::B]) &&
+       !self.ends_with(&[Twist::Binv, Twist::Ainv]) &&
+       !self.ends_with(&[Twist::A, Twist::B, Twist::A]) {
       new_twists.push(Twist::Binv);
       res.push(Braid { twists: new_twists.clone() });
       new_twists.pop();
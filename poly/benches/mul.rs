@@ -0,0 +1,94 @@
+// Compares the `*` operator on `Poly` with the multiplication it replaced, a
+// double loop inserting every product of terms into a `HashMap`, and with the
+// dense quadratic `mul_naive`. The operands are shaped like the Kauffman
+// brackets of 3-braids of canonical length 10 to 20, which have 33 to 53
+// terms, and of far longer braids. Most of the gain over the hash map comes
+// from the dense layout, and from shifting instead of multiplying by
+// monomials. Karatsuba adds to it for `Zp` and `BigInt`, but `i64` always
+// takes the schoolbook algorithm, as its intermediate sums could overflow.
+// Run with `cargo bench`.
+
+extern crate poly;
+
+use std::collections::HashMap;
+use std::hint::black_box;
+use std::time::{Duration, Instant};
+
+use poly::{BigInt, Poly, PolyRng, Ring, Zp};
+
+const ITERATIONS: u32 = 200;
+
+// Modulus of the `Zp` benchmarks, the one used by the multi-modular DP.
+const PRIME: u64 = 4611686018427387847;
+
+// Dense polynomial with pseudorandom coefficients like those of the bracket
+// of a braid of length `n`, whose exponents lie in -(n + 6) ... n + 6.
+fn bracket_like<R: Ring>(n: i64, seed: u64) -> Poly<R> {
+  PolyRng::new(seed).poly(-n - 6, 2 * n as usize + 13, 1 << 23)
+}
+
+// Nonzero terms of `poly`, stored the way `Poly` stored them before it was
+// made dense.
+fn hash_terms<R: Ring>(poly: &Poly<R>) -> HashMap<i64, R> {
+  poly.terms().map(|(k, v)| (k, v.clone())).collect()
+}
+
+// The multiplication `Poly` had before it was made dense.
+fn mul_hash<R: Ring>(a: &HashMap<i64, R>, b: &HashMap<i64, R>)
+    -> HashMap<i64, R> {
+  let mut res = HashMap::new();
+  for (k1, v1) in a.iter() {
+    for (k2, v2) in b.iter() {
+      res.entry(k1 + k2).or_insert_with(R::zero).add_product(v1, v2);
+    }
+  }
+  res.shrink_to_fit();
+  res
+}
+
+// Average time of `f` over `ITERATIONS` runs.
+fn time<F: FnMut()>(mut f: F) -> Duration {
+  let start = Instant::now();
+  for _ in 0..ITERATIONS {
+    f();
+  }
+  start.elapsed() / ITERATIONS
+}
+
+fn speedup(old: Duration, new: Duration) -> f64 {
+  old.as_secs_f64() / new.as_secs_f64()
+}
+
+// Times the product of `x` and `y` by all three algorithms.
+fn report<R: Ring>(name: &str, n: i64, x: &Poly<R>, y: &Poly<R>) {
+  let (hx, hy) = (hash_terms(x), hash_terms(y));
+  let hash = time(|| { black_box(mul_hash(black_box(&hx), black_box(&hy))); });
+  let naive = time(|| { black_box(x.mul_naive(black_box(y))); });
+  let fast = time(|| { black_box(x * black_box(y)); });
+  println!("{:<14} n = {:>3}: hash map {:>10.2?}, naive {:>10.2?}, \
+            fast {:>10.2?}, speedup {:>5.1}x over hash map, \
+            {:>4.1}x over naive",
+           name, n, hash, naive, fast, speedup(hash, fast),
+           speedup(naive, fast));
+}
+
+fn bench<R: Ring>(name: &str) {
+  let shift = Poly::<R>::identity();
+  for n in 10..21 {
+    let x = bracket_like::<R>(n, 1);
+    let y = bracket_like::<R>(n, 2);
+    report(&format!("{} shift", name), n, &x, &shift);
+    report(&format!("{} product", name), n, &x, &y);
+  }
+  for &n in [50, 100, 200].iter() {
+    let x = bracket_like::<R>(n, 1);
+    let y = bracket_like::<R>(n, 2);
+    report(&format!("{} product", name), n, &x, &y);
+  }
+}
+
+fn main() {
+  bench::<i64>("i64");
+  bench::<Zp<PRIME>>("Zp");
+  bench::<BigInt>("BigInt");
+}
@@ -0,0 +1,442 @@
+use std::cmp::Ordering;
+use std::fmt;
+use std::str::FromStr;
+
+use ring::Ring;
+
+/// Arbitrary-precision signed integer, stored as a sign and a magnitude of
+/// base 2^32 digits (least significant first, without trailing zeros).
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+pub struct BigInt {
+  negative: bool,
+  mag: Vec<u32>,
+}
+
+/// Error returned when parsing a `BigInt` from a malformed string.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct ParseBigIntError;
+
+fn cmp_mag(a: &[u32], b: &[u32]) -> Ordering {
+  if a.len() != b.len() {
+    return a.len().cmp(&b.len());
+  }
+  for i in (0..a.len()).rev() {
+    if a[i] != b[i] {
+      return a[i].cmp(&b[i]);
+    }
+  }
+  Ordering::Equal
+}
+
+// a += b.
+fn add_mag(a: &mut Vec<u32>, b: &[u32]) {
+  if a.len() < b.len() {
+    a.resize(b.len(), 0);
+  }
+  let mut carry = 0_u64;
+  for (i, digit) in a.iter_mut().enumerate() {
+    if i >= b.len() && carry == 0 {
+      break;
+    }
+    let sum = *digit as u64 + b.get(i).cloned().unwrap_or(0) as u64 + carry;
+    *digit = sum as u32;
+    carry = sum >> 32;
+  }
+  if carry != 0 {
+    a.push(carry as u32);
+  }
+}
+
+// a -= b, requires a >= b.
+fn sub_mag(a: &mut Vec<u32>, b: &[u32]) {
+  let mut borrow = 0_i64;
+  for (i, digit) in a.iter_mut().enumerate() {
+    if i >= b.len() && borrow == 0 {
+      break;
+    }
+    let diff = *digit as i64 - b.get(i).cloned().unwrap_or(0) as i64 - borrow;
+    if diff < 0 {
+      *digit = (diff + (1_i64 << 32)) as u32;
+      borrow = 1;
+    } else {
+      *digit = diff as u32;
+      borrow = 0;
+    }
+  }
+  trim_mag(a);
+}
+
+fn mul_mag(a: &[u32], b: &[u32]) -> Vec<u32> {
+  if a.is_empty() || b.is_empty() {
+    return Vec::new();
+  }
+  let mut res = vec![0_u32; a.len() + b.len()];
+  for (i, x) in a.iter().enumerate() {
+    let mut carry = 0_u64;
+    for (j, y) in b.iter().enumerate() {
+      let cur = res[i + j] as u64 + (*x as u64) * (*y as u64) + carry;
+      res[i + j] = cur as u32;
+      carry = cur >> 32;
+    }
+    res[i + b.len()] = carry as u32;
+  }
+  trim_mag(&mut res);
+  res
+}
+
+// a = a * m + c.
+fn mul_small_add_mag(a: &mut Vec<u32>, m: u32, c: u32) {
+  let mut carry = c as u64;
+  for digit in a.iter_mut() {
+    let cur = (*digit as u64) * (m as u64) + carry;
+    *digit = cur as u32;
+    carry = cur >> 32;
+  }
+  if carry != 0 {
+    a.push(carry as u32);
+  }
+  trim_mag(a);
+}
+
+// a /= d, returns the remainder.
+fn div_small_mag(a: &mut Vec<u32>, d: u32) -> u32 {
+  let mut rem = 0_u64;
+  for digit in a.iter_mut().rev() {
+    let cur = (rem << 32) | (*digit as u64);
+    *digit = (cur / d as u64) as u32;
+    rem = cur % d as u64;
+  }
+  trim_mag(a);
+  rem as u32
+}
+
+// Long division (Knuth's algorithm D), returns the quotient and the remainder.
+// Requires nonempty `b`.
+fn divrem_mag(a: &[u32], b: &[u32]) -> (Vec<u32>, Vec<u32>) {
+  if cmp_mag(a, b) == Ordering::Less {
+    return (Vec::new(), a.to_vec());
+  }
+  if b.len() == 1 {
+    let mut quot = a.to_vec();
+    let rem = div_small_mag(&mut quot, b[0]);
+    let mut rem = vec![rem];
+    trim_mag(&mut rem);
+    return (quot, rem);
+  }
+  // Normalize so that the top digit of the divisor has its high bit set.
+  let shift = b[b.len() - 1].leading_zeros();
+  let v = shl_mag(b, shift);
+  let mut u = shl_mag(a, shift);
+  u.resize(a.len() + 1, 0);
+  let n = v.len();
+  let m = a.len() - n;
+  let base = 1_u128 << 32;
+  let mut quot = vec![0_u32; m + 1];
+  for j in (0..m + 1).rev() {
+    let num = ((u[j + n] as u128) << 32) | u[j + n - 1] as u128;
+    let mut qhat = num / v[n - 1] as u128;
+    let mut rhat = num % v[n - 1] as u128;
+    while qhat >= base ||
+          qhat * v[n - 2] as u128 > ((rhat << 32) | u[j + n - 2] as u128) {
+      qhat -= 1;
+      rhat += v[n - 1] as u128;
+      if rhat >= base {
+        break;
+      }
+    }
+    // Subtract qhat * v from the current window of u.
+    let mut borrow = 0_i64;
+    let mut carry = 0_u64;
+    for i in 0..n {
+      let prod = qhat as u64 * v[i] as u64 + carry;
+      carry = prod >> 32;
+      let diff = u[i + j] as i64 - borrow - (prod & 0xffff_ffff) as i64;
+      u[i + j] = diff as u32;
+      borrow = if diff < 0 { 1 } else { 0 };
+    }
+    let diff = u[j + n] as i64 - borrow - carry as i64;
+    u[j + n] = diff as u32;
+    if diff < 0 {
+      // qhat was one too large, add v back.
+      qhat -= 1;
+      let mut carry = 0_u64;
+      for i in 0..n {
+        let sum = u[i + j] as u64 + v[i] as u64 + carry;
+        u[i + j] = sum as u32;
+        carry = sum >> 32;
+      }
+      u[j + n] = u[j + n].wrapping_add(carry as u32);
+    }
+    quot[j] = qhat as u32;
+  }
+  trim_mag(&mut quot);
+  u.truncate(n);
+  let rem = shr_mag(&u, shift);
+  (quot, rem)
+}
+
+// a * 2^shift, for shift < 32.
+fn shl_mag(a: &[u32], shift: u32) -> Vec<u32> {
+  let mut res = Vec::with_capacity(a.len() + 1);
+  let mut carry = 0_u32;
+  for digit in a.iter() {
+    res.push((digit << shift) | carry);
+    carry = if shift == 0 { 0 } else { digit >> (32 - shift) };
+  }
+  if carry != 0 {
+    res.push(carry);
+  }
+  res
+}
+
+// a / 2^shift, for shift < 32.
+fn shr_mag(a: &[u32], shift: u32) -> Vec<u32> {
+  let mut res = vec![0_u32; a.len()];
+  for i in 0..a.len() {
+    let high = if shift == 0 || i + 1 == a.len() {
+      0
+    } else {
+      a[i + 1] << (32 - shift)
+    };
+    res[i] = (a[i] >> shift) | high;
+  }
+  trim_mag(&mut res);
+  res
+}
+
+fn trim_mag(a: &mut Vec<u32>) {
+  while a.last() == Some(&0) {
+    a.pop();
+  }
+}
+
+impl BigInt {
+  /// Gives the number as `i64`, or `None` if it does not fit.
+  pub fn to_i64(&self) -> Option<i64> {
+    if self.mag.len() > 2 {
+      return None;
+    }
+    let mut abs = 0_u64;
+    for (i, digit) in self.mag.iter().enumerate() {
+      abs |= (*digit as u64) << (32 * i);
+    }
+    if self.negative {
+      if abs <= 1 << 63 {
+        Some((abs as i64).wrapping_neg())
+      } else {
+        None
+      }
+    } else if abs < 1 << 63 {
+      Some(abs as i64)
+    } else {
+      None
+    }
+  }
+
+  /// Gives the absolute value of the number.
+  pub fn abs(&self) -> BigInt {
+    BigInt { negative: false, mag: self.mag.clone() }
+  }
+
+  /// Gives the quotient rounded towards zero and the remainder (which has
+  /// the sign of `self`), like the `/` and `%` operators on primitive
+  /// integers. Panics if `rhs` is zero.
+  pub fn div_rem(&self, rhs: &BigInt) -> (BigInt, BigInt) {
+    assert!(!rhs.mag.is_empty(), "BigInt division by zero");
+    let (quot, rem) = divrem_mag(&self.mag, &rhs.mag);
+    let mut quot = BigInt { negative: self.negative ^ rhs.negative, mag: quot };
+    let mut rem = BigInt { negative: self.negative, mag: rem };
+    quot.normalize();
+    rem.normalize();
+    (quot, rem)
+  }
+
+  /// Gives the nonnegative greatest common divisor of `self` and `rhs`.
+  pub fn gcd(&self, rhs: &BigInt) -> BigInt {
+    let mut a = self.abs();
+    let mut b = rhs.abs();
+    while !b.mag.is_empty() {
+      let (_, rem) = a.div_rem(&b);
+      a = b;
+      b = rem;
+    }
+    a
+  }
+
+  /// Gives the nearest `f64` to the number (infinite if it is too large).
+  pub fn to_f64(&self) -> f64 {
+    let mut res = 0.0;
+    for digit in self.mag.iter().rev() {
+      res = res * 4294967296.0 + *digit as f64;
+    }
+    if self.negative { -res } else { res }
+  }
+
+  fn normalize(&mut self) {
+    trim_mag(&mut self.mag);
+    if self.mag.is_empty() {
+      self.negative = false;
+    }
+  }
+
+  // self += (-1)^negative * mag.
+  fn add_signed(&mut self, negative: bool, mag: &[u32]) {
+    if self.negative == negative {
+      add_mag(&mut self.mag, mag);
+    } else if cmp_mag(&self.mag, mag) != Ordering::Less {
+      sub_mag(&mut self.mag, mag);
+    } else {
+      let mut res = mag.to_vec();
+      sub_mag(&mut res, &self.mag);
+      self.mag = res;
+      self.negative = negative;
+    }
+    self.normalize();
+  }
+}
+
+impl From<i64> for BigInt {
+  fn from(num: i64) -> BigInt {
+    let abs = num.unsigned_abs();
+    let mut res = BigInt {
+      negative: num < 0,
+      mag: vec![abs as u32, (abs >> 32) as u32],
+    };
+    res.normalize();
+    res
+  }
+}
+
+impl From<i32> for BigInt {
+  fn from(num: i32) -> BigInt {
+    BigInt::from(num as i64)
+  }
+}
+
+impl From<i128> for BigInt {
+  fn from(num: i128) -> BigInt {
+    let abs = num.unsigned_abs();
+    let mut res = BigInt {
+      negative: num < 0,
+      mag: (0..4).map(|i| (abs >> (32 * i)) as u32).collect(),
+    };
+    res.normalize();
+    res
+  }
+}
+
+impl Ord for BigInt {
+  fn cmp(&self, rhs: &BigInt) -> Ordering {
+    match (self.negative, rhs.negative) {
+      (false, true) => Ordering::Greater,
+      (true, false) => Ordering::Less,
+      (false, false) => cmp_mag(&self.mag, &rhs.mag),
+      (true, true) => cmp_mag(&rhs.mag, &self.mag),
+    }
+  }
+}
+
+impl PartialOrd for BigInt {
+  fn partial_cmp(&self, rhs: &BigInt) -> Option<Ordering> {
+    Some(self.cmp(rhs))
+  }
+}
+
+impl fmt::Display for BigInt {
+  fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+    if self.mag.is_empty() {
+      return write!(f, "0");
+    }
+    // Peel off base 10^9 chunks, least significant first.
+    let mut chunks = Vec::new();
+    let mut mag = self.mag.clone();
+    while !mag.is_empty() {
+      chunks.push(div_small_mag(&mut mag, 1_000_000_000));
+    }
+    if self.negative {
+      write!(f, "-")?;
+    }
+    write!(f, "{}", chunks.pop().unwrap())?;
+    for chunk in chunks.iter().rev() {
+      write!(f, "{:09}", chunk)?;
+    }
+    Ok(())
+  }
+}
+
+impl FromStr for BigInt {
+  type Err = ParseBigIntError;
+  fn from_str(s: &str) -> Result<BigInt, ParseBigIntError> {
+    let (negative, digits) = match s.as_bytes().first() {
+      Some(b'-') => (true, &s[1..]),
+      Some(b'+') => (false, &s[1..]),
+      _ => (false, s),
+    };
+    if digits.is_empty() {
+      return Err(ParseBigIntError);
+    }
+    let mut res = BigInt { negative, mag: Vec::new() };
+    for c in digits.bytes() {
+      if !c.is_ascii_digit() {
+        return Err(ParseBigIntError);
+      }
+      mul_small_add_mag(&mut res.mag, 10, (c - b'0') as u32);
+    }
+    res.normalize();
+    Ok(res)
+  }
+}
+
+impl Ring for BigInt {
+  fn zero() -> BigInt {
+    BigInt { negative: false, mag: Vec::new() }
+  }
+
+  fn one() -> BigInt {
+    BigInt { negative: false, mag: vec![1] }
+  }
+
+  fn from_i64(num: i64) -> BigInt {
+    BigInt::from(num)
+  }
+
+  fn is_zero(&self) -> bool {
+    self.mag.is_empty()
+  }
+
+  fn is_negative(&self) -> bool {
+    self.negative
+  }
+
+  fn add_in_place(&mut self, rhs: &BigInt) {
+    self.add_signed(rhs.negative, &rhs.mag);
+  }
+
+  fn sub_in_place(&mut self, rhs: &BigInt) {
+    self.add_signed(!rhs.negative, &rhs.mag);
+  }
+
+  fn mul_in_place(&mut self, rhs: &BigInt) {
+    self.mag = mul_mag(&self.mag, &rhs.mag);
+    self.negative ^= rhs.negative;
+    self.normalize();
+  }
+
+  fn neg_in_place(&mut self) {
+    self.negative = !self.negative;
+    self.normalize();
+  }
+
+  fn div_exact(&self, rhs: &BigInt) -> Option<BigInt> {
+    if rhs.is_zero() {
+      return None;
+    }
+    let (quot, rem) = self.div_rem(rhs);
+    if rem.is_zero() { Some(quot) } else { None }
+  }
+
+  fn add_product(&mut self, a: &BigInt, b: &BigInt) {
+    let prod = mul_mag(&a.mag, &b.mag);
+    self.add_signed(a.negative ^ b.negative, &prod);
+  }
+}
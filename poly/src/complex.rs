@@ -0,0 +1,110 @@
+use std::fmt;
+use std::ops::{Add, Div, Mul, Neg, Sub};
+
+/// Complex number with `f64` parts, used for numerical evaluation.
+#[derive(Clone, Copy, Debug, PartialEq, Default)]
+pub struct Complex {
+  pub re: f64,
+  pub im: f64,
+}
+
+impl Complex {
+  /// Gives the number `re` + `im` i.
+  pub fn new(re: f64, im: f64) -> Complex {
+    Complex { re, im }
+  }
+
+  /// Gives the number with absolute value `r` and argument `theta`.
+  pub fn from_polar(r: f64, theta: f64) -> Complex {
+    Complex { re: r * theta.cos(), im: r * theta.sin() }
+  }
+
+  /// Gives the primitive root of unity e^(2 pi i `k` / `n`).
+  pub fn root_of_unity(n: u64, k: i64) -> Complex {
+    let theta = 2.0 * std::f64::consts::PI * k as f64 / n as f64;
+    Complex::from_polar(1.0, theta)
+  }
+
+  /// Gives the absolute value.
+  pub fn abs(self) -> f64 {
+    self.re.hypot(self.im)
+  }
+
+  /// Gives the complex conjugate.
+  pub fn conj(self) -> Complex {
+    Complex { re: self.re, im: -self.im }
+  }
+
+  /// Gives `self` raised to the integer power `exp`, by repeated squaring.
+  pub fn powi(self, exp: i64) -> Complex {
+    let mut base = if exp < 0 { Complex::new(1.0, 0.0) / self } else { self };
+    let mut exp = exp.unsigned_abs();
+    let mut res = Complex::new(1.0, 0.0);
+    while exp > 0 {
+      if exp & 1 == 1 {
+        res = res * base;
+      }
+      base = base * base;
+      exp >>= 1;
+    }
+    res
+  }
+}
+
+impl From<f64> for Complex {
+  fn from(re: f64) -> Complex {
+    Complex { re, im: 0.0 }
+  }
+}
+
+impl Add for Complex {
+  type Output = Complex;
+  fn add(self, rhs: Complex) -> Complex {
+    Complex { re: self.re + rhs.re, im: self.im + rhs.im }
+  }
+}
+
+impl Sub for Complex {
+  type Output = Complex;
+  fn sub(self, rhs: Complex) -> Complex {
+    Complex { re: self.re - rhs.re, im: self.im - rhs.im }
+  }
+}
+
+impl Mul for Complex {
+  type Output = Complex;
+  fn mul(self, rhs: Complex) -> Complex {
+    Complex {
+      re: self.re * rhs.re - self.im * rhs.im,
+      im: self.re * rhs.im + self.im * rhs.re,
+    }
+  }
+}
+
+impl Div for Complex {
+  type Output = Complex;
+  fn div(self, rhs: Complex) -> Complex {
+    let norm = rhs.re * rhs.re + rhs.im * rhs.im;
+    Complex {
+      re: (self.re * rhs.re + self.im * rhs.im) / norm,
+      im: (self.im * rhs.re - self.re * rhs.im) / norm,
+    }
+  }
+}
+
+impl Neg for Complex {
+  type Output = Complex;
+  fn neg(self) -> Complex {
+    Complex { re: -self.re, im: -self.im }
+  }
+}
+
+impl fmt::Display for Complex {
+  fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+    if self.im < 0.0 {
+      write!(f, "{} - {}i", self.re, -self.im)
+    } else {
+      write!(f, "{} + {}i", self.re, self.im)
+    }
+  }
+}
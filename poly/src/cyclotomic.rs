@@ -0,0 +1,226 @@
+use std::fmt;
+use std::ops::{Add, Mul, Neg, Sub};
+
+use complex::Complex;
+use format::Notation;
+use frac::FracPoly;
+use rational::Rational;
+use ring::{IntegerRing, Ring};
+use Poly;
+
+// Gives the Möbius function of `n`: zero unless `n` is square-free, and
+// otherwise -1 to the number of its prime factors.
+fn moebius(mut n: u64) -> i64 {
+  let mut res = 1;
+  let mut p = 2;
+  while p * p <= n {
+    if n.is_multiple_of(p) {
+      n /= p;
+      if n.is_multiple_of(p) {
+        return 0;
+      }
+      res = -res;
+    }
+    p += 1;
+  }
+  if n > 1 { -res } else { res }
+}
+
+impl<R: Ring> Poly<R> {
+  /// Gives the `n`-th cyclotomic polynomial, the minimal polynomial of the
+  /// primitive `n`-th roots of unity, as the product of (t^d - 1)^mu(n / d)
+  /// over the divisors d of `n`. Panics if `n` is zero.
+  pub fn cyclotomic(n: u64) -> Poly<R> {
+    assert!(n > 0, "cyclotomic polynomial of order zero");
+    let mut numer = Poly::number(R::one());
+    let mut denom = Poly::number(R::one());
+    for d in (1..n + 1).filter(|&d| n.is_multiple_of(d)) {
+      let mut factor = Poly::monomial(R::one(), d as i64);
+      factor.set_coef(0, R::from_i64(-1));
+      match moebius(n / d) {
+        1 => numer *= &factor,
+        -1 => denom *= &factor,
+        _ => (),
+      }
+    }
+    numer.div_exact(&denom).expect("cyclotomic polynomials are exact")
+  }
+}
+
+/// Element of the cyclotomic field Q(z), where z = e^(2 pi i / `order`),
+/// for exact values of polynomials at roots of unity. It is stored as the
+/// polynomial in z of degree below phi(`order`), the degree of the field.
+/// Arithmetic on elements of different fields panics.
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+pub struct Cyclotomic {
+  order: u64,
+  // Reduced modulo the cyclotomic polynomial, in the variable z.
+  value: Poly<Rational>,
+}
+
+impl Cyclotomic {
+  /// Gives P(z) for z = e^(2 pi i / `order`). Panics if `order` is zero.
+  pub fn from_poly(order: u64, poly: &Poly<Rational>) -> Cyclotomic {
+    assert!(order > 0, "cyclotomic field of order zero");
+    // Since z^order = 1, the exponents only matter modulo `order`.
+    let mut coefs = vec![Rational::zero(); order as usize];
+    for (k, c) in poly.terms() {
+      coefs[k.rem_euclid(order as i64) as usize].add_in_place(c);
+    }
+    // Then the leading terms are eliminated by the cyclotomic polynomial,
+    // which is monic.
+    let modulus = Poly::<i64>::cyclotomic(order);
+    let degree = modulus.max_degree().unwrap() as usize;
+    for i in (degree..coefs.len()).rev() {
+      let top = coefs[i].clone();
+      for (k, m) in modulus.terms().take(degree) {
+        let mut prod = top.clone();
+        prod.mul_in_place(&Rational::from(*m));
+        coefs[i - degree + k as usize].sub_in_place(&prod);
+      }
+    }
+    coefs.truncate(degree);
+    Cyclotomic { order, value: Poly::from_coefs(0, coefs).with_var('z') }
+  }
+
+  /// Gives the rational number `num` as an element of Q(z).
+  pub fn rational(order: u64, num: Rational) -> Cyclotomic {
+    Cyclotomic::from_poly(order, &Poly::number(num))
+  }
+
+  /// Gives the generator z = e^(2 pi i / `order`) itself.
+  pub fn zeta(order: u64) -> Cyclotomic {
+    Cyclotomic::from_poly(order, &Poly::identity())
+  }
+
+  /// Gives the order of the root of unity z generating the field.
+  pub fn order(&self) -> u64 {
+    self.order
+  }
+
+  /// Gives the element as the unique polynomial in z of degree below
+  /// phi(`order`).
+  pub fn value(&self) -> &Poly<Rational> {
+    &self.value
+  }
+
+  /// Checks whether the element is zero.
+  pub fn is_zero(&self) -> bool {
+    self.value.is_zero()
+  }
+
+  /// Gives the element as a rational number, if it is one.
+  pub fn to_rational(&self) -> Option<Rational> {
+    match self.value.max_degree() {
+      Some(k) if k > 0 => None,
+      _ => Some(self.value.get_coef(0)),
+    }
+  }
+
+  /// Gives the complex conjugate, which replaces z by z^-1.
+  pub fn conj(&self) -> Cyclotomic {
+    Cyclotomic::from_poly(self.order, &self.value.mirror())
+  }
+
+  /// Gives the multiplicative inverse, or `None` for zero.
+  pub fn inverse(&self) -> Option<Cyclotomic> {
+    if self.is_zero() {
+      return None;
+    }
+    // Extended Euclid over Q[z, z^-1], keeping s with s * value = r modulo
+    // the cyclotomic polynomial. That is irreducible, so the last nonzero
+    // remainder is a unit, that is a monomial.
+    let modulus = Poly::cyclotomic(self.order).with_var('z');
+    let (mut r0, mut r1) = (modulus, self.value.clone());
+    let (mut s0, mut s1) = (Poly::zero(), Poly::number(Rational::one()));
+    while !r1.is_zero() {
+      let (quot, rem) = r0.div_rem(&r1).expect("nonzero rationals are units");
+      r0 = r1;
+      r1 = rem;
+      let s2 = &s0 - &(&quot * &s1);
+      s0 = s1;
+      s1 = s2;
+    }
+    let unit = r0.pow(-1).expect("remainder is a unit");
+    Some(Cyclotomic::from_poly(self.order, &(&s0 * &unit)))
+  }
+
+  /// Gives the value as a floating-point complex number.
+  pub fn to_complex(&self) -> Complex {
+    self.value.terms().fold(Complex::default(), |acc, (k, c)| {
+      acc + Complex::root_of_unity(self.order, k) * Complex::from(c.to_f64())
+    })
+  }
+
+  // Gives the common order of `self` and `rhs`, panicking if they differ.
+  fn expect_common_order(&self, rhs: &Cyclotomic) -> u64 {
+    assert_eq!(self.order, rhs.order,
+               "elements of cyclotomic fields of different orders");
+    self.order
+  }
+}
+
+impl Add for &Cyclotomic {
+  type Output = Cyclotomic;
+  fn add(self, rhs: &Cyclotomic) -> Cyclotomic {
+    let order = self.expect_common_order(rhs);
+    Cyclotomic { order, value: &self.value + &rhs.value }
+  }
+}
+
+impl Sub for &Cyclotomic {
+  type Output = Cyclotomic;
+  fn sub(self, rhs: &Cyclotomic) -> Cyclotomic {
+    let order = self.expect_common_order(rhs);
+    Cyclotomic { order, value: &self.value - &rhs.value }
+  }
+}
+
+impl Neg for &Cyclotomic {
+  type Output = Cyclotomic;
+  fn neg(self) -> Cyclotomic {
+    Cyclotomic { order: self.order, value: -&self.value }
+  }
+}
+
+impl Mul for &Cyclotomic {
+  type Output = Cyclotomic;
+  fn mul(self, rhs: &Cyclotomic) -> Cyclotomic {
+    let order = self.expect_common_order(rhs);
+    Cyclotomic::from_poly(order, &(&self.value * &rhs.value))
+  }
+}
+
+/// Prints the element as a polynomial in z in the SageMath notation, like
+/// `1 + 1/2*z - z^(3)`.
+impl fmt::Display for Cyclotomic {
+  fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+    write!(f, "{}", self.value.display(Notation::Sage))
+  }
+}
+
+impl<R: IntegerRing> Poly<R> {
+  /// Evaluates P(z^`k`) exactly in Q(z), for z = e^(2 pi i / `n`). Panics
+  /// if `n` is zero.
+  pub fn eval_at_root_of_unity(&self, n: u64, k: i64) -> Cyclotomic {
+    assert!(n > 0, "cyclotomic field of order zero");
+    let n_exp = n as i64;
+    let poly: Poly<Rational> = self.terms()
+      .map(|(e, c)| {
+        let exp = (e % n_exp) * (k % n_exp) % n_exp;
+        (exp, Rational::from(c.to_bigint()))
+      })
+      .collect();
+    Cyclotomic::from_poly(n, &poly)
+  }
+}
+
+impl<R: IntegerRing> FracPoly<R> {
+  /// Evaluates P(t) exactly at t = e^(2 pi i `k` / `n`), taking
+  /// t^(1/denom) = e^(2 pi i `k` / (`n` denom)), so the value lies in the
+  /// cyclotomic field of order `n` times the denominator. Panics if `n` is
+  /// zero.
+  pub fn eval_at_root_of_unity(&self, n: u64, k: i64) -> Cyclotomic {
+    self.numer_poly().eval_at_root_of_unity(n * self.denom() as u64, k)
+  }
+}
@@ -0,0 +1,186 @@
+use error::PolyError;
+use ring::{IntegerRing, Ring};
+use Poly;
+
+// Content of a coefficient list: the nonnegative gcd of its elements, or
+// `None` if it is not representable. The first nonzero element is taken with
+// its sign, so that the most negative value of a fixed-width ring only fails
+// when every nonzero element equals it.
+fn coefs_content<'a, R, I>(coefs: I) -> Option<R>
+    where R: IntegerRing + 'a, I: IntoIterator<Item = &'a R> {
+  let mut nonzero = coefs.into_iter().filter(|c| !c.is_zero());
+  let first = match nonzero.next() {
+    Some(first) => first.clone(),
+    None => return Some(R::zero()),
+  };
+  nonzero.try_fold(first, |acc, c| acc.checked_gcd(c))
+    .and_then(|acc| acc.checked_gcd(&R::zero()))
+}
+
+// Divides every element of `coefs` by their content. When the content is
+// not representable, every element is a multiple of the magnitude of the
+// most negative value, so halving them first brings it into range.
+fn make_primitive<R: IntegerRing>(coefs: &mut [R]) {
+  let content = match coefs_content(coefs.iter()) {
+    Some(content) => content,
+    None => {
+      for c in coefs.iter_mut() {
+        *c = c.div_exact(&R::from_i64(2)).unwrap();
+      }
+      return make_primitive(coefs);
+    },
+  };
+  if content.is_zero() || content.is_one() {
+    return;
+  }
+  for c in coefs.iter_mut() {
+    *c = c.div_exact(&content).unwrap();
+  }
+}
+
+// Pseudo-remainder of ordinary polynomials `a` and `b` (lowest coefficient
+// first), that is the remainder of lc(b)^k * a divided by `b` for some k.
+// Requires nonempty `b` without trailing zeros. Fails if the coefficient
+// arithmetic overflows.
+fn pseudo_rem<R: Ring>(a: &[R], b: &[R]) -> Result<Vec<R>, PolyError> {
+  let lead = b.last().unwrap();
+  let mut rem = a.to_vec();
+  while rem.len() >= b.len() {
+    let top = rem.pop().unwrap();
+    let shift = rem.len() + 1 - b.len();
+    for (i, c) in rem.iter_mut().enumerate() {
+      *c = c.checked_mul(lead)
+        .ok_or(PolyError::Overflow { exp: i as i64 })?;
+    }
+    for (i, c) in b[..b.len() - 1].iter().enumerate() {
+      let exp = shift + i;
+      rem[exp] = top.checked_mul(c)
+        .and_then(|prod| rem[exp].checked_sub(&prod))
+        .ok_or(PolyError::Overflow { exp: exp as i64 })?;
+    }
+    while rem.last().is_some_and(R::is_zero) {
+      rem.pop();
+    }
+  }
+  Ok(rem)
+}
+
+impl<R: Ring> Poly<R> {
+  /// Divides the polynomial by the monomial `coef` * t^`exp`. Fails unless
+  /// every coefficient is divisible by `coef`.
+  pub fn div_monomial(&self, coef: &R, exp: i64) -> Result<Poly<R>, PolyError> {
+    if coef.is_zero() {
+      return Err(PolyError::DivisionByZero);
+    }
+    let mut coefs = Vec::with_capacity(self.coefs.len());
+    for c in self.coefs.iter() {
+      coefs.push(c.div_exact(coef).ok_or(PolyError::InexactDivision)?);
+    }
+    Ok(Poly::from_coefs(self.min_exp - exp, coefs).with_var(self.var))
+  }
+
+  /// Divides the polynomial by `divisor`, whose leading coefficient must be
+  /// a unit. Gives the quotient q and the remainder r such that
+  /// `self` = q * `divisor` + r, where the exponents of r lie in
+  /// `min_degree(self)` ... `min_degree(self) + span(divisor) - 1`.
+  pub fn div_rem(&self, divisor: &Poly<R>)
+      -> Result<(Poly<R>, Poly<R>), PolyError> {
+    self.common_var(divisor)?;
+    match divisor.leading_coef() {
+      None => Err(PolyError::DivisionByZero),
+      Some(lead) if lead.inverse().is_none() => Err(PolyError::NotInvertible),
+      Some(_) => self.long_division(divisor),
+    }
+  }
+
+  /// Gives `self` / `divisor`, failing if the division leaves a remainder.
+  pub fn div_exact(&self, divisor: &Poly<R>) -> Result<Poly<R>, PolyError> {
+    self.common_var(divisor)?;
+    if divisor.is_zero() {
+      return Err(PolyError::DivisionByZero);
+    }
+    match self.long_division(divisor) {
+      Ok((quot, ref rem)) if rem.is_zero() => Ok(quot),
+      _ => Err(PolyError::InexactDivision),
+    }
+  }
+
+  // Schoolbook division of the ordinary polynomials t^-min_exp * P(t), from
+  // the highest power down. Fails if some leading coefficient of the running
+  // remainder is not divisible by the leading coefficient of `divisor`.
+  fn long_division(&self, divisor: &Poly<R>)
+      -> Result<(Poly<R>, Poly<R>), PolyError> {
+    let var = self.common_var(divisor)?;
+    let n = divisor.coefs.len();
+    if self.coefs.len() < n {
+      return Ok((Poly::zero().with_var(var), self.clone().with_var(var)));
+    }
+    let lead = &divisor.coefs[n - 1];
+    let mut rem = self.coefs.clone();
+    let mut quot = vec![R::zero(); rem.len() - n + 1];
+    for i in (0..quot.len()).rev() {
+      if rem[i + n - 1].is_zero() {
+        continue;
+      }
+      let q = rem[i + n - 1].div_exact(lead).ok_or(PolyError::NotInvertible)?;
+      for (j, c) in divisor.coefs.iter().enumerate() {
+        let mut prod = q.clone();
+        prod.mul_in_place(c);
+        rem[i + j].sub_in_place(&prod);
+      }
+      quot[i] = q;
+    }
+    rem.truncate(n - 1);
+    Ok((Poly::from_coefs(self.min_exp - divisor.min_exp, quot).with_var(var),
+        Poly::from_coefs(self.min_exp, rem).with_var(var)))
+  }
+}
+
+impl<R: IntegerRing> Poly<R> {
+  /// Gives the content: the nonnegative gcd of all coefficients, or `None`
+  /// if it is not representable, as for `i64::MIN * t`.
+  pub fn content(&self) -> Option<R> {
+    coefs_content(self.coefs.iter())
+  }
+
+  /// Gives the primitive part: the polynomial divided by its content.
+  pub fn primitive_part(&self) -> Poly<R> {
+    let mut res = self.clone();
+    make_primitive(&mut res.coefs);
+    res
+  }
+
+  /// Gives the greatest common divisor in Z[t, t^-1]. It is only defined up
+  /// to units ±t^k, so the result is normalized to have the lowest exponent
+  /// zero and a positive leading coefficient. Fails if the polynomials are
+  /// in different variables, or if the coefficients overflow. For
+  /// fixed-width rings, the remainder sequence can overflow even when the
+  /// gcd itself is small, which `BigInt` coefficients avoid.
+  pub fn gcd(&self, rhs: &Poly<R>) -> Result<Poly<R>, PolyError> {
+    let var = self.common_var(rhs)?;
+    let mut a = self.primitive_part().coefs;
+    let mut b = rhs.primitive_part().coefs;
+    if a.len() < b.len() {
+      std::mem::swap(&mut a, &mut b);
+    }
+    // Primitive polynomial remainder sequence.
+    while !b.is_empty() {
+      let mut rem = pseudo_rem(&a, &b)?;
+      make_primitive(&mut rem);
+      a = b;
+      b = rem;
+    }
+    // The content of the gcd is that of all coefficients of both operands.
+    let mut content =
+      coefs_content(self.coefs.iter().chain(rhs.coefs.iter()))
+        .ok_or(PolyError::Overflow { exp: a.len() as i64 - 1 })?;
+    if a.last().is_some_and(R::is_negative) {
+      content.neg_in_place();
+    }
+    for (i, c) in a.iter_mut().enumerate() {
+      *c = c.checked_mul(&content)
+        .ok_or(PolyError::Overflow { exp: i as i64 })?;
+    }
+    Ok(Poly::from_coefs(0, a).with_var(var))
+  }
+}
@@ -0,0 +1,252 @@
+use std::error::Error;
+use std::fmt;
+use std::io::{self, Read, Write};
+
+use bigint::BigInt;
+use ring::{IntegerRing, Ring};
+use Poly;
+
+// Version of the binary format written by `Poly::write_to`. The format is
+// the version byte, the variable as an ASCII byte, the lowest exponent as a
+// zigzag varint, the number of coefficients as a varint, and then the
+// coefficients from the lowest exponent up as zigzag varints. Varints hold 7
+// bits per byte, least significant first, with the high bit set on all but
+// the last byte; zigzag maps 0, -1, 1, -2, ... to 0, 1, 2, 3, ...
+const FORMAT_VERSION: u8 = 1;
+
+// Longest varint holding a `u64`.
+const MAX_VARINT_LEN: usize = 10;
+
+// Longest varint of a coefficient, good for integers of 28671 bits. Longer
+// ones are taken for corrupt data rather than read into a `BigInt`.
+const MAX_COEF_BYTES: usize = 4096;
+
+// Upper limit on the coefficients preallocated before they are read, so that
+// a corrupt count cannot exhaust the memory.
+const MAX_PREALLOCATED: usize = 1 << 16;
+
+/// Error produced by `Poly::read_from` on invalid input.
+#[derive(Debug)]
+pub enum DecodePolyError {
+  /// The input ended before the first byte, as it does after the last
+  /// polynomial of a stream.
+  EndOfInput,
+  /// The input ended in the middle of a polynomial.
+  Truncated,
+  /// The data was written by an unknown version of the format.
+  UnsupportedVersion(u8),
+  /// The variable byte is not an ASCII letter.
+  InvalidVariable(u8),
+  /// A varint is longer than its type allows.
+  VarintOverflow,
+  /// The exponents do not fit in `i64`.
+  ExpOverflow,
+  /// A coefficient is not representable in the coefficient ring.
+  CoefOverflow,
+  /// The highest or the lowest coefficient is zero, or the zero polynomial
+  /// has a nonzero exponent, which the encoder never writes.
+  NotNormalized,
+  /// Reading from the underlying reader failed.
+  Io(io::Error),
+}
+
+impl fmt::Display for DecodePolyError {
+  fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+    match *self {
+      DecodePolyError::EndOfInput => write!(f, "no more polynomials"),
+      DecodePolyError::Truncated => write!(f, "truncated polynomial data"),
+      DecodePolyError::UnsupportedVersion(v) =>
+        write!(f, "unsupported polynomial format version {}", v),
+      DecodePolyError::InvalidVariable(b) =>
+        write!(f, "invalid variable byte {:#04x}", b),
+      DecodePolyError::VarintOverflow => write!(f, "varint is too long"),
+      DecodePolyError::ExpOverflow => write!(f, "exponent out of range"),
+      DecodePolyError::CoefOverflow => write!(f, "coefficient out of range"),
+      DecodePolyError::NotNormalized =>
+        write!(f, "polynomial is not normalized"),
+      DecodePolyError::Io(ref err) => write!(f, "read failed: {}", err),
+    }
+  }
+}
+
+impl Error for DecodePolyError {
+  fn source(&self) -> Option<&(dyn Error + 'static)> {
+    match *self {
+      DecodePolyError::Io(ref err) => Some(err),
+      _ => None,
+    }
+  }
+}
+
+fn write_varint<W: Write>(w: &mut W, mut value: u64) -> io::Result<()> {
+  let mut buf = [0_u8; MAX_VARINT_LEN];
+  let mut len = 0;
+  while value >= 0x80 {
+    buf[len] = (value as u8) | 0x80;
+    value >>= 7;
+    len += 1;
+  }
+  buf[len] = value as u8;
+  w.write_all(&buf[..len + 1])
+}
+
+fn zigzag(value: i64) -> u64 {
+  ((value << 1) ^ (value >> 63)) as u64
+}
+
+fn unzigzag(value: u64) -> i64 {
+  ((value >> 1) as i64) ^ -((value & 1) as i64)
+}
+
+// Writes the integer `value` of any size as a zigzag varint. With
+// `value` = 2 m + s for the sign bit s, the first byte holds 6 bits of m.
+fn write_zigzag_bigint<W: Write>(w: &mut W, value: &BigInt) -> io::Result<()> {
+  if let Some(small) = value.to_i64() {
+    return write_varint(w, zigzag(small));
+  }
+  // The magnitude part m: `value` for nonnegative ones, -`value` - 1 for
+  // negative ones.
+  let mut m = value.clone();
+  let sign = if value.is_negative() {
+    m.neg_in_place();
+    m.sub_in_place(&BigInt::one());
+    1
+  } else {
+    0
+  };
+  let (quot, rem) = m.div_rem(&BigInt::from(64));
+  let mut bytes = vec![(rem.to_i64().unwrap() as u8) << 1 | sign];
+  m = quot;
+  while !m.is_zero() {
+    let (quot, rem) = m.div_rem(&BigInt::from(128));
+    bytes.push(rem.to_i64().unwrap() as u8);
+    m = quot;
+  }
+  if bytes.len() > MAX_COEF_BYTES {
+    return Err(io::Error::new(io::ErrorKind::InvalidInput,
+                              "coefficient too large to encode"));
+  }
+  let last = bytes.len() - 1;
+  for b in bytes[..last].iter_mut() {
+    *b |= 0x80;
+  }
+  w.write_all(&bytes)
+}
+
+// Reads one byte, mapping the end of input to `at_end`.
+fn read_byte<Rd: Read>(r: &mut Rd, at_end: DecodePolyError)
+    -> Result<u8, DecodePolyError> {
+  let mut buf = [0_u8; 1];
+  loop {
+    match r.read(&mut buf) {
+      Ok(0) => return Err(at_end),
+      Ok(_) => return Ok(buf[0]),
+      Err(ref err) if err.kind() == io::ErrorKind::Interrupted => (),
+      Err(err) => return Err(DecodePolyError::Io(err)),
+    }
+  }
+}
+
+fn read_varint<Rd: Read>(r: &mut Rd) -> Result<u64, DecodePolyError> {
+  let mut value: u64 = 0;
+  for i in 0..MAX_VARINT_LEN {
+    let b = read_byte(r, DecodePolyError::Truncated)?;
+    let g = b & 0x7f;
+    if i == MAX_VARINT_LEN - 1 && g > 1 {
+      return Err(DecodePolyError::VarintOverflow);
+    }
+    value |= (g as u64) << (7 * i);
+    if b & 0x80 == 0 {
+      return Ok(value);
+    }
+  }
+  Err(DecodePolyError::VarintOverflow)
+}
+
+// Reads a zigzag varint of up to `MAX_COEF_BYTES` into the ring `R`, failing
+// as soon as it does not fit.
+fn read_zigzag_ring<R: Ring, Rd: Read>(r: &mut Rd)
+    -> Result<R, DecodePolyError> {
+  // The value is 2 m + s, with m = (g0 >> 1) + 64 g1 + 64 128 g2 + ...,
+  // summed from the lowest group up. `scale` is the weight of the next
+  // group, `None` once it no longer fits in `R`.
+  let overflow = || DecodePolyError::CoefOverflow;
+  let first = read_byte(r, DecodePolyError::Truncated)?;
+  let mut m = R::from_i64(((first & 0x7f) >> 1) as i64);
+  let mut scale = Some(R::from_i64(64));
+  let mut more = first & 0x80 != 0;
+  let mut len = 1;
+  while more {
+    if len == MAX_COEF_BYTES {
+      return Err(DecodePolyError::VarintOverflow);
+    }
+    let b = read_byte(r, DecodePolyError::Truncated)?;
+    len += 1;
+    more = b & 0x80 != 0;
+    let g = R::from_i64((b & 0x7f) as i64);
+    if !g.is_zero() {
+      let term = scale.as_ref().and_then(|s| s.checked_mul(&g))
+        .ok_or_else(overflow)?;
+      m = m.checked_add(&term).ok_or_else(overflow)?;
+    }
+    scale = scale.and_then(|s| s.checked_mul(&R::from_i64(128)));
+  }
+  if first & 1 == 1 {
+    m.neg_in_place();
+    m = m.checked_sub(&R::one()).ok_or_else(overflow)?;
+  }
+  Ok(m)
+}
+
+impl<R: IntegerRing> Poly<R> {
+  /// Writes the polynomial in the compact binary format, which
+  /// `read_from` reads back. Polynomials can be written one after another
+  /// into the same stream, which should be buffered: the encoding writes
+  /// every varint separately. Fails with `io::ErrorKind::InvalidInput`,
+  /// after writing part of the polynomial, on coefficients of 28672 bits
+  /// and more, which `read_from` would reject.
+  pub fn write_to<W: Write>(&self, w: &mut W) -> io::Result<()> {
+    w.write_all(&[FORMAT_VERSION, self.var as u8])?;
+    write_varint(w, zigzag(self.min_exp))?;
+    write_varint(w, self.coefs.len() as u64)?;
+    for c in self.coefs.iter() {
+      write_zigzag_bigint(w, &c.to_bigint())?;
+    }
+    Ok(())
+  }
+}
+
+impl<R: Ring> Poly<R> {
+  /// Reads a polynomial written by `write_to`. Gives
+  /// `DecodePolyError::EndOfInput` if the reader is already exhausted, so
+  /// that a stream of polynomials can be read until that error. The reader
+  /// should be buffered, since it is read byte by byte.
+  pub fn read_from<Rd: Read>(r: &mut Rd) -> Result<Poly<R>, DecodePolyError> {
+    let version = read_byte(r, DecodePolyError::EndOfInput)?;
+    if version != FORMAT_VERSION {
+      return Err(DecodePolyError::UnsupportedVersion(version));
+    }
+    let var = read_byte(r, DecodePolyError::Truncated)?;
+    if !var.is_ascii_alphabetic() {
+      return Err(DecodePolyError::InvalidVariable(var));
+    }
+    let min_exp = unzigzag(read_varint(r)?);
+    let len = read_varint(r)?;
+    if len > i64::MAX as u64
+        || (len > 0 && min_exp.checked_add(len as i64 - 1).is_none()) {
+      return Err(DecodePolyError::ExpOverflow);
+    }
+    let mut coefs = Vec::with_capacity((len as usize).min(MAX_PREALLOCATED));
+    for _ in 0..len {
+      coefs.push(read_zigzag_ring::<R, Rd>(r)?);
+    }
+    let normalized = match (coefs.first(), coefs.last()) {
+      (Some(first), Some(last)) => !first.is_zero() && !last.is_zero(),
+      _ => min_exp == 0,
+    };
+    if !normalized {
+      return Err(DecodePolyError::NotNormalized);
+    }
+    Ok(Poly { min_exp, coefs, var: var as char })
+  }
+}
@@ -0,0 +1,42 @@
+use std::error::Error;
+use std::fmt;
+
+/// Error produced by fallible operations on `Poly`.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum PolyError {
+  /// Coefficient arithmetic overflowed while computing the t^`exp` term.
+  Overflow { exp: i64 },
+  /// An exponent of the result does not fit in `i64`.
+  ExpOverflow,
+  /// Negative powers of zero were needed.
+  DivisionByZero,
+  /// Negative powers of a value which is not a unit in the coefficient ring
+  /// were needed.
+  NotInvertible,
+  /// The dividend is not a multiple of the divisor.
+  InexactDivision,
+  /// A substitution of a fractional power produced non-integer exponents.
+  InexactSubstitution,
+  /// The operands are polynomials in different variables.
+  VariableMismatch { lhs: char, rhs: char },
+}
+
+impl fmt::Display for PolyError {
+  fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+    match *self {
+      PolyError::Overflow { exp } =>
+        write!(f, "coefficient overflow at t^{}", exp),
+      PolyError::ExpOverflow => write!(f, "exponent out of range"),
+      PolyError::DivisionByZero => write!(f, "division by zero"),
+      PolyError::NotInvertible =>
+        write!(f, "value is not invertible in the coefficient ring"),
+      PolyError::InexactDivision => write!(f, "division is not exact"),
+      PolyError::InexactSubstitution =>
+        write!(f, "substitution gives non-integer exponents"),
+      PolyError::VariableMismatch { lhs, rhs } =>
+        write!(f, "polynomials in different variables {} and {}", lhs, rhs),
+    }
+  }
+}
+
+impl Error for PolyError {}
@@ -0,0 +1,63 @@
+use complex::Complex;
+use error::PolyError;
+use rational::Rational;
+use ring::{self, IntegerRing, Ring};
+use Poly;
+
+impl<R: Ring> Poly<R> {
+  /// Evaluates P(`x`) in the coefficient ring. Negative powers of `x` need
+  /// its inverse, so they fail unless `x` is a unit. Fails with the exponent
+  /// of the term being added if the coefficient arithmetic overflows.
+  pub fn eval(&self, x: &R) -> Result<R, PolyError> {
+    let mut res = R::zero();
+    for (i, c) in self.coefs.iter().enumerate().rev() {
+      let overflow = PolyError::Overflow { exp: self.min_exp + i as i64 };
+      res = res.checked_mul(x).and_then(|r| r.checked_add(c))
+        .ok_or(overflow)?;
+    }
+    if self.min_exp == 0 || (self.min_exp > 0 && res.is_zero()) {
+      return Ok(res);
+    }
+    let shift = if self.min_exp > 0 {
+      x.clone()
+    } else if x.is_zero() {
+      return Err(PolyError::DivisionByZero);
+    } else {
+      x.inverse().ok_or(PolyError::NotInvertible)?
+    };
+    ring::checked_pow(&shift, self.min_exp.unsigned_abs())
+      .and_then(|power| res.checked_mul(&power))
+      .ok_or(PolyError::Overflow { exp: self.min_exp })
+  }
+}
+
+impl<R: IntegerRing> Poly<R> {
+  /// Evaluates P(`x`) exactly at a rational point.
+  pub fn eval_rational(&self, x: &Rational) -> Result<Rational, PolyError> {
+    self.map_coefs(|c| Rational::from(c.to_bigint())).eval(x)
+  }
+
+  /// Evaluates P(`x`) in floating point.
+  pub fn eval_f64(&self, x: f64) -> Result<f64, PolyError> {
+    if x == 0.0 && self.min_exp < 0 {
+      return Err(PolyError::DivisionByZero);
+    }
+    let mut res = 0.0;
+    for c in self.coefs.iter().rev() {
+      res = res * x + c.to_f64();
+    }
+    Ok(res * x.powf(self.min_exp as f64))
+  }
+
+  /// Evaluates P(`z`) at a complex point in floating point.
+  pub fn eval_complex(&self, z: Complex) -> Result<Complex, PolyError> {
+    if z == Complex::default() && self.min_exp < 0 {
+      return Err(PolyError::DivisionByZero);
+    }
+    let mut res = Complex::default();
+    for c in self.coefs.iter().rev() {
+      res = res * z + Complex::from(c.to_f64());
+    }
+    Ok(res * z.powi(self.min_exp))
+  }
+}
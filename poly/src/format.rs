@@ -0,0 +1,134 @@
+use std::fmt;
+
+use ring::{IntegerRing, Ring};
+use Poly;
+
+/// Output notation for `Poly::display`.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum Notation {
+  /// `P(t) = 1 * t^-2  +  1 * t^0  +  -3 * t^1`, as printed by `to_string`.
+  Plain,
+  /// LaTeX math mode: `t^{-2} + 1 - 3t`.
+  Latex,
+  /// Mathematica input: `t^-2 + 1 - 3*t`.
+  Mathematica,
+  /// SageMath and PARI/GP input: `t^(-2) + 1 - 3*t`.
+  Sage,
+  /// Jones polynomial syntax of the KnotInfo database: `t^(-2)+1-3*t`.
+  KnotInfo,
+}
+
+/// Helper returned by `Poly::display`, formats the polynomial in the chosen
+/// notation.
+pub struct PolyDisplay<'a, R: Ring + 'a> {
+  poly: &'a Poly<R>,
+  // Denominator of the exponents: the polynomial is printed in t^(1/denom).
+  denom: i64,
+  notation: Notation,
+}
+
+impl<'a, R: Ring> PolyDisplay<'a, R> {
+  pub(crate) fn with_denom(poly: &'a Poly<R>, denom: i64, notation: Notation)
+      -> PolyDisplay<'a, R> {
+    PolyDisplay { poly, denom, notation }
+  }
+}
+
+impl<R: Ring> Poly<R> {
+  /// Gives an object which formats the polynomial in `notation`.
+  pub fn display(&self, notation: Notation) -> PolyDisplay<'_, R> {
+    PolyDisplay::with_denom(self, 1, notation)
+  }
+}
+
+fn fmt_plain<R: Ring>(poly: &Poly<R>, denom: i64, f: &mut fmt::Formatter)
+    -> fmt::Result {
+  write!(f, "P({}) = ", poly.var)?;
+  let mut first = true;
+  for (k, v) in poly.terms() {
+    if first {
+      first = false;
+    } else {
+      write!(f, "  +  ")?;
+    }
+    write!(f, "{} * ", v)?;
+    fmt_power(Notation::Plain, poly.var, k, denom, f)?;
+  }
+  Ok(())
+}
+
+// Writes `var`^(`exp` / `denom`). Only the plain notation spells out the
+// first and the zeroth powers.
+fn fmt_power(notation: Notation, var: char, exp: i64, denom: i64,
+             f: &mut fmt::Formatter) -> fmt::Result {
+  let factor = if denom == 1 { 1 } else { exp.gcd(&denom) };
+  let (exp, denom) = (exp / factor, denom / factor);
+  if denom != 1 {
+    return match notation {
+      Notation::Latex => write!(f, "{}^{{{}/{}}}", var, exp, denom),
+      _ => write!(f, "{}^({}/{})", var, exp, denom),
+    };
+  }
+  if exp == 1 && notation != Notation::Plain {
+    return write!(f, "{}", var);
+  }
+  match notation {
+    Notation::Latex => write!(f, "{}^{{{}}}", var, exp),
+    Notation::Sage | Notation::KnotInfo => write!(f, "{}^({})", var, exp),
+    _ => write!(f, "{}^{}", var, exp),
+  }
+}
+
+// Writes the terms in a computer algebra notation: the signs are folded into
+// the operators, and unit coefficients are omitted.
+fn fmt_terms<R: Ring>(poly: &Poly<R>, denom: i64, notation: Notation,
+                      f: &mut fmt::Formatter) -> fmt::Result {
+  let (plus, minus, times) = match notation {
+    Notation::Latex => (" + ", " - ", ""),
+    Notation::KnotInfo => ("+", "-", "*"),
+    _ => (" + ", " - ", "*"),
+  };
+  let mut first = true;
+  for (k, v) in poly.terms() {
+    // The sign is stripped from the text, since negating the most negative
+    // value of a fixed-width ring overflows.
+    let text = v.to_string();
+    let abs = if v.is_negative() {
+      write!(f, "{}", if first { "-" } else { minus })?;
+      text.trim_start_matches('-')
+    } else {
+      if !first {
+        write!(f, "{}", plus)?;
+      }
+      &text
+    };
+    first = false;
+    if k == 0 {
+      write!(f, "{}", abs)?;
+    } else {
+      if abs != "1" {
+        write!(f, "{}{}", abs, times)?;
+      }
+      fmt_power(notation, poly.var, k, denom, f)?;
+    }
+  }
+  if first {
+    write!(f, "0")?;
+  }
+  Ok(())
+}
+
+impl<'a, R: Ring> fmt::Display for PolyDisplay<'a, R> {
+  fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+    match self.notation {
+      Notation::Plain => fmt_plain(self.poly, self.denom, f),
+      notation => fmt_terms(self.poly, self.denom, notation, f),
+    }
+  }
+}
+
+impl<R: Ring> fmt::Display for Poly<R> {
+  fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+    fmt_plain(self, 1, f)
+  }
+}
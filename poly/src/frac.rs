@@ -0,0 +1,158 @@
+use error::PolyError;
+use format::{Notation, PolyDisplay};
+use ring::{IntegerRing, Ring};
+use Poly;
+
+/// Laurent polynomial in t with fractional exponents sharing a common
+/// denominator, such as the Jones polynomial of a link with an even number of
+/// components, which lies in Z[t^(1/2), t^(-1/2)]. It is stored as P(s) with
+/// s = t^(1/`denom`), and the denominator is always kept as small as possible,
+/// so polynomials are equal exactly when their denominators and their
+/// polynomials in s are.
+#[derive(Clone, Debug, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub struct FracPoly<R: Ring = i64> {
+  // Positive denominator of the exponents, coprime with their gcd.
+  denom: i64,
+  // The polynomial in s = t^(1/denom).
+  poly: Poly<R>,
+}
+
+impl<R: Ring> FracPoly<R> {
+  /// Gives P(t^(1/`denom`)). Panics unless `denom` is positive.
+  pub fn new(poly: Poly<R>, denom: i64) -> FracPoly<R> {
+    assert!(denom > 0, "FracPoly with nonpositive denominator");
+    let mut res = FracPoly { denom, poly };
+    res.normalize();
+    res
+  }
+
+  /// Gives the denominator of the exponents, in lowest terms.
+  pub fn denom(&self) -> i64 {
+    self.denom
+  }
+
+  /// Gives the polynomial P(s) in s = t^(1/`denom()`).
+  pub fn numer_poly(&self) -> &Poly<R> {
+    &self.poly
+  }
+
+  /// Gives the coefficient in front of the t^(`num` / `den`) power.
+  pub fn get_coef(&self, num: i64, den: i64) -> R {
+    if den == 0 || (num * self.denom) % den != 0 {
+      return R::zero();
+    }
+    self.poly.get_coef(num * self.denom / den)
+  }
+
+  /// Checks whether all exponents are integers.
+  pub fn is_integral(&self) -> bool {
+    self.denom == 1
+  }
+
+  /// Gives the polynomial as an ordinary `Poly` in t, or `None` unless all
+  /// exponents are integers.
+  pub fn to_poly(&self) -> Option<Poly<R>> {
+    if self.is_integral() { Some(self.poly.clone()) } else { None }
+  }
+
+  /// Gives the polynomial P(s) in s = t^(1/`denom`), or `None` unless
+  /// `denom` is a positive multiple of `denom()`.
+  pub fn to_denom(&self, denom: i64) -> Option<Poly<R>> {
+    if denom <= 0 || denom % self.denom != 0 {
+      return None;
+    }
+    Some(self.poly.substitute_power(denom / self.denom))
+  }
+
+  /// Gets the mirror polynomial M(t) = P(t^-1).
+  pub fn mirror(&self) -> FracPoly<R> {
+    FracPoly { denom: self.denom, poly: self.poly.mirror() }
+  }
+
+  /// Gives an object which formats the polynomial in `notation`, writing the
+  /// fractional exponents as `t^(1/2)` (or `t^{1/2}` in LaTeX).
+  pub fn display(&self, notation: Notation) -> PolyDisplay<'_, R> {
+    PolyDisplay::with_denom(&self.poly, self.denom, notation)
+  }
+
+  /// Gives `self + rhs`, or an error if the coefficient arithmetic overflowed.
+  pub fn checked_add(&self, rhs: &FracPoly<R>)
+      -> Result<FracPoly<R>, PolyError> {
+    let (lhs, rhs, denom) = self.common_denom(rhs);
+    Ok(FracPoly::new(lhs.checked_add(&rhs)?, denom))
+  }
+
+  /// Gives `self - rhs`, or an error if the coefficient arithmetic overflowed.
+  pub fn checked_sub(&self, rhs: &FracPoly<R>)
+      -> Result<FracPoly<R>, PolyError> {
+    let (lhs, rhs, denom) = self.common_denom(rhs);
+    Ok(FracPoly::new(lhs.checked_sub(&rhs)?, denom))
+  }
+
+  /// Gives `self * rhs`, or an error if the coefficient arithmetic overflowed.
+  pub fn checked_mul(&self, rhs: &FracPoly<R>)
+      -> Result<FracPoly<R>, PolyError> {
+    let (lhs, rhs, denom) = self.common_denom(rhs);
+    Ok(FracPoly::new(lhs.checked_mul(&rhs)?, denom))
+  }
+
+  // Both operands as polynomials in t^(1/denom), where denom is the least
+  // common multiple of their denominators.
+  fn common_denom(&self, rhs: &FracPoly<R>) -> (Poly<R>, Poly<R>, i64) {
+    let denom = self.denom / self.denom.gcd(&rhs.denom) * rhs.denom;
+    (self.to_denom(denom).unwrap(), rhs.to_denom(denom).unwrap(), denom)
+  }
+
+  // Divides the denominator and all exponents by their common factor.
+  fn normalize(&mut self) {
+    let factor = self.poly.terms()
+      .fold(self.denom, |acc, (k, _)| acc.gcd(&k));
+    if factor > 1 {
+      self.poly = self.poly.substitute_fractional_power(1, factor).unwrap();
+      self.denom /= factor;
+    }
+  }
+}
+
+impl<R: Ring> From<Poly<R>> for FracPoly<R> {
+  fn from(poly: Poly<R>) -> FracPoly<R> {
+    FracPoly { denom: 1, poly }
+  }
+}
+
+impl<R: Ring> std::ops::Add for &FracPoly<R> {
+  type Output = FracPoly<R>;
+  fn add(self, rhs: &FracPoly<R>) -> FracPoly<R> {
+    let (lhs, rhs, denom) = self.common_denom(rhs);
+    FracPoly::new(&lhs + &rhs, denom)
+  }
+}
+
+impl<R: Ring> std::ops::Neg for &FracPoly<R> {
+  type Output = FracPoly<R>;
+  fn neg(self) -> FracPoly<R> {
+    FracPoly { denom: self.denom, poly: -&self.poly }
+  }
+}
+
+impl<R: Ring> std::ops::Sub for &FracPoly<R> {
+  type Output = FracPoly<R>;
+  fn sub(self, rhs: &FracPoly<R>) -> FracPoly<R> {
+    let (lhs, rhs, denom) = self.common_denom(rhs);
+    FracPoly::new(&lhs - &rhs, denom)
+  }
+}
+
+impl<R: Ring> std::ops::Mul for &FracPoly<R> {
+  type Output = FracPoly<R>;
+  fn mul(self, rhs: &FracPoly<R>) -> FracPoly<R> {
+    let (lhs, rhs, denom) = self.common_denom(rhs);
+    FracPoly::new(&lhs * &rhs, denom)
+  }
+}
+
+impl<R: Ring> std::fmt::Display for FracPoly<R> {
+  fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+    write!(f, "{}", self.display(Notation::Plain))
+  }
+}
@@ -1,189 +1,528 @@
 #[cfg(test)]
 mod test;
 
-use std::collections::HashMap;
+mod bigint;
+mod complex;
+mod cyclotomic;
+mod division;
+mod encode;
+mod error;
+mod eval;
+mod format;
+mod frac;
+mod matrix;
+mod modular;
+mod mul;
+mod ops;
+mod multi;
+mod parse;
+mod random;
+mod rational;
+mod ring;
+mod roots;
+mod series;
+mod substitute;
+mod symmetry;
 
-/// Laurent Polynomial in one variable (t), with integer coefficients.
-#[derive(Clone, Debug)]
-pub struct Poly {
-  coef_map: HashMap<i64, i64>,
-}
+use std::cmp::Ordering;
+use std::hash::{Hash, Hasher};
 
-impl PartialEq for Poly {
-  fn eq(&self, rhs: &Poly) -> bool {
-    for (k, v) in self.coef_map.iter() {
-      if *v != rhs.coef_map.get(k).cloned().unwrap_or(0) {
-        return false;
-      }
-    }
-    for (k, v) in rhs.coef_map.iter() {
-      if *v != self.coef_map.get(k).cloned().unwrap_or(0) {
-        return false;
-      }
-    }
-    true
-  }
-}
+pub use bigint::{BigInt, ParseBigIntError};
+pub use complex::Complex;
+pub use cyclotomic::Cyclotomic;
+pub use encode::DecodePolyError;
+pub use error::PolyError;
+pub use format::{Notation, PolyDisplay};
+pub use frac::FracPoly;
+pub use matrix::PolyMatrix;
+pub use modular::{crt, CrtPoly, Zp};
+pub use multi::MultiPoly;
+pub use parse::{ParsePolyError, ParsePolyErrorKind};
+pub use random::PolyRng;
+pub use rational::Rational;
+pub use ring::{IntegerRing, Ring};
+pub use roots::Root;
+pub use series::Series;
 
-impl Eq for Poly {}
+/// Laurent Polynomial in one named variable (t by default), with coefficients
+/// in the ring `R`. The storage is always normalized, so polynomials are
+/// equal exactly when they have the same terms and, unless they are
+/// constants, the same variable. Polynomials are ordered by their lowest
+/// exponent first, then by the coefficients starting from that exponent, then
+/// by the variable of nonconstant ones.
+///
+/// Arithmetic between polynomials in different variables is an error, except
+/// that constants combine with polynomials in any variable, and give them
+/// their variable. Constants in different variables give a constant in t.
+/// The operators panic on a mismatch, and the `checked_*` methods report it.
+#[derive(Clone, Debug)]
+pub struct Poly<R: Ring = i64> {
+  // Exponent of the first element of `coefs` (zero for the zero polynomial).
+  min_exp: i64,
+  // Coefficients in front of t^min_exp, t^(min_exp + 1), and so on. The first
+  // and the last ones are never zero.
+  coefs: Vec<R>,
+  // Name of the variable, an ASCII letter.
+  var: char,
+}
 
-impl Poly {
+impl<R: Ring> Poly<R> {
   /// Gives the P(t) = 0 polynomial.
-  pub fn zero() -> Poly {
-    Poly { coef_map: HashMap::new() }
+  pub fn zero() -> Poly<R> {
+    Poly { min_exp: 0, coefs: Vec::new(), var: 't' }
   }
 
   /// Gives the P(t) = `num` polynomial.
-  pub fn number(num: i64) -> Poly {
-    let mut coef_map = HashMap::with_capacity(1);
-    coef_map.insert(0, num);
-    coef_map.shrink_to_fit();
-    Poly { coef_map }
+  pub fn number(num: R) -> Poly<R> {
+    Poly::from_coefs(0, vec![num])
   }
 
   /// Gives the P(t) = t polynomial.
-  pub fn identity() -> Poly {
-    let mut coef_map = HashMap::with_capacity(1);
-    coef_map.insert(1, 1);
-    coef_map.shrink_to_fit();
-    Poly { coef_map }
+  pub fn identity() -> Poly<R> {
+    Poly::from_coefs(1, vec![R::one()])
   }
 
   /// Gives the P(t) = t^-1 polynomial.
-  pub fn inverse_identity() -> Poly {
-    let mut coef_map = HashMap::with_capacity(1);
-    coef_map.insert(-1, 1);
-    coef_map.shrink_to_fit();
-    Poly { coef_map }
+  pub fn inverse_identity() -> Poly<R> {
+    Poly::from_coefs(-1, vec![R::one()])
+  }
+
+  /// Gives the P(t) = `coef` * t^`exp` polynomial.
+  pub fn monomial(coef: R, exp: i64) -> Poly<R> {
+    Poly::from_coefs(exp, vec![coef])
+  }
+
+  /// Gives the polynomial with `coefs[i]` in front of the t^(`min_exp` + i)
+  /// power.
+  pub fn from_coefs(min_exp: i64, coefs: Vec<R>) -> Poly<R> {
+    let mut res = Poly { min_exp, coefs, var: 't' };
+    res.normalize();
+    res
+  }
+
+  /// Gives the same polynomial in the variable `var`. Panics unless `var` is
+  /// an ASCII letter.
+  pub fn with_var(mut self, var: char) -> Poly<R> {
+    assert!(var.is_ascii_alphabetic(), "Poly variable must be a letter");
+    self.var = var;
+    self
+  }
+
+  /// Gives the name of the variable.
+  pub fn var(&self) -> char {
+    self.var
+  }
+
+  /// Checks whether the polynomial has no terms with nonzero exponents, and
+  /// hence combines with polynomials in any variable.
+  pub fn is_constant(&self) -> bool {
+    self.coefs.is_empty() || (self.min_exp == 0 && self.coefs.len() == 1)
   }
 
   /// Gets the coefficient in front of the t^`exp` power.
-  pub fn get_coef(&self, exp: i64) -> i64 {
-    self.coef_map.get(&exp).cloned().unwrap_or(0)
+  pub fn get_coef(&self, exp: i64) -> R {
+    if exp < self.min_exp {
+      return R::zero();
+    }
+    self.coefs.get((exp - self.min_exp) as usize).cloned()
+      .unwrap_or_else(R::zero)
   }
 
   /// Sets the coefficient in front of the t^`exp` power to `coef`.
-  pub fn set_coef(&mut self, exp: i64, coef: i64) {
-    self.coef_map.insert(exp, coef);
+  pub fn set_coef(&mut self, exp: i64, coef: R) {
+    if coef.is_zero() && self.get_coef(exp).is_zero() {
+      return;
+    }
+    self.reserve_exps(exp, exp);
+    let ind = (exp - self.min_exp) as usize;
+    self.coefs[ind] = coef;
+    self.normalize();
+  }
+
+  /// Checks whether P(t) = 0.
+  pub fn is_zero(&self) -> bool {
+    self.coefs.is_empty()
+  }
+
+  /// Checks whether the polynomial has exactly one nonzero term c * t^k.
+  pub fn is_monomial(&self) -> bool {
+    self.coefs.len() == 1
+  }
+
+  /// Gives the lowest exponent with a nonzero coefficient, or `None` for the
+  /// zero polynomial.
+  pub fn min_degree(&self) -> Option<i64> {
+    if self.is_zero() { None } else { Some(self.min_exp) }
+  }
+
+  /// Gives the highest exponent with a nonzero coefficient, or `None` for the
+  /// zero polynomial.
+  pub fn max_degree(&self) -> Option<i64> {
+    if self.is_zero() { None } else { Some(self.max_exp()) }
+  }
+
+  /// Gives the difference between the highest and the lowest exponents, or
+  /// `None` for the zero polynomial.
+  pub fn span(&self) -> Option<i64> {
+    if self.is_zero() { None } else { Some(self.coefs.len() as i64 - 1) }
+  }
+
+  /// Gives the coefficient in front of the highest power, or `None` for the
+  /// zero polynomial.
+  pub fn leading_coef(&self) -> Option<&R> {
+    self.coefs.last()
+  }
+
+  /// Gives the coefficient in front of the lowest power, or `None` for the
+  /// zero polynomial.
+  pub fn trailing_coef(&self) -> Option<&R> {
+    self.coefs.first()
+  }
+
+  /// Iterates over the nonzero terms as (exponent, coefficient) pairs, in the
+  /// ascending order of exponents.
+  pub fn terms(&self) -> impl DoubleEndedIterator<Item = (i64, &R)> {
+    let min_exp = self.min_exp;
+    self.coefs.iter().enumerate()
+      .filter(|&(_, v)| !v.is_zero())
+      .map(move |(i, v)| (min_exp + i as i64, v))
   }
 
   /// Gets the mirror polynomial for P(t): M(t) = P(t^-1 ).
-  pub fn mirror(&self) -> Poly {
-    let mut coef_map = HashMap::with_capacity(self.coef_map.len());
-    for (k, v) in self.coef_map.iter() {
-      coef_map.insert(-(*k), *v);
+  pub fn mirror(&self) -> Poly<R> {
+    if self.coefs.is_empty() {
+      return Poly::zero().with_var(self.var);
     }
-    Poly { coef_map }
+    let mut coefs = self.coefs.clone();
+    coefs.reverse();
+    Poly { min_exp: -self.max_exp(), coefs, var: self.var }
+  }
+
+  /// Gives the polynomial with `f` applied to every coefficient, possibly
+  /// changing the coefficient ring.
+  pub fn map_coefs<S: Ring, F: FnMut(&R) -> S>(&self, f: F) -> Poly<S> {
+    Poly::from_coefs(self.min_exp, self.coefs.iter().map(f).collect())
+      .with_var(self.var)
+  }
+
+  /// Gives `self + rhs`, or an error naming the exponent at which the
+  /// coefficient arithmetic overflowed, or the mismatching variables.
+  pub fn checked_add(&self, rhs: &Poly<R>) -> Result<Poly<R>, PolyError> {
+    let var = self.common_var(rhs)?;
+    if rhs.coefs.is_empty() {
+      return Ok(self.clone().with_var(var));
+    }
+    let mut res = self.clone().with_var(var);
+    res.reserve_exps(rhs.min_exp, rhs.max_exp());
+    let offset = (rhs.min_exp - res.min_exp) as usize;
+    for (i, v) in rhs.coefs.iter().enumerate() {
+      let exp = rhs.min_exp + i as i64;
+      res.coefs[offset + i] = res.coefs[offset + i].checked_add(v)
+        .ok_or(PolyError::Overflow { exp })?;
+    }
+    res.normalize();
+    Ok(res)
   }
-}
 
-impl ToString for Poly {
-  fn to_string(&self) -> String {
-    let mut indices = Vec::new();
-    for (k, v) in self.coef_map.iter() {
-      if *v != 0 {
-        indices.push((*k, *v));
+  /// Gives `self - rhs`, or an error naming the exponent at which the
+  /// coefficient arithmetic overflowed, or the mismatching variables.
+  pub fn checked_sub(&self, rhs: &Poly<R>) -> Result<Poly<R>, PolyError> {
+    let var = self.common_var(rhs)?;
+    if rhs.coefs.is_empty() {
+      return Ok(self.clone().with_var(var));
+    }
+    let mut res = self.clone().with_var(var);
+    res.reserve_exps(rhs.min_exp, rhs.max_exp());
+    let offset = (rhs.min_exp - res.min_exp) as usize;
+    for (i, v) in rhs.coefs.iter().enumerate() {
+      let exp = rhs.min_exp + i as i64;
+      res.coefs[offset + i] = res.coefs[offset + i].checked_sub(v)
+        .ok_or(PolyError::Overflow { exp })?;
+    }
+    res.normalize();
+    Ok(res)
+  }
+
+  /// Gives `self * rhs`, or an error naming the exponent at which the
+  /// coefficient arithmetic overflowed, or the mismatching variables.
+  pub fn checked_mul(&self, rhs: &Poly<R>) -> Result<Poly<R>, PolyError> {
+    let var = self.common_var(rhs)?;
+    if self.coefs.is_empty() || rhs.coefs.is_empty() {
+      return Ok(Poly::zero().with_var(var));
+    }
+    if rhs.is_monomial() {
+      let res = self.checked_mul_monomial(&rhs.coefs[0], rhs.min_exp)?;
+      return Ok(res.with_var(var));
+    }
+    if self.is_monomial() {
+      let res = rhs.checked_mul_monomial(&self.coefs[0], self.min_exp)?;
+      return Ok(res.with_var(var));
+    }
+    let min_exp = self.min_exp + rhs.min_exp;
+    let mut coefs = vec![R::zero(); self.coefs.len() + rhs.coefs.len() - 1];
+    for (i, v1) in self.coefs.iter().enumerate() {
+      for (j, v2) in rhs.coefs.iter().enumerate() {
+        let overflow = PolyError::Overflow { exp: min_exp + (i + j) as i64 };
+        let prod = v1.checked_mul(v2).ok_or_else(|| overflow.clone())?;
+        coefs[i + j] = coefs[i + j].checked_add(&prod).ok_or(overflow)?;
       }
     }
-    indices.sort();
-    let mut res = "P(t) = ".to_string();
-    let mut first = true;
-    for (k, v) in indices {
-      if first {
-        first = false;
-      } else {
-        res.push_str("  +  ");
+    Ok(Poly::from_coefs(min_exp, coefs).with_var(var))
+  }
+
+  /// Gives `self * scalar`, or an error naming the exponent at which the
+  /// coefficient arithmetic overflowed.
+  pub fn checked_scale(&self, scalar: &R) -> Result<Poly<R>, PolyError> {
+    let mut res = self.clone();
+    for (i, v) in res.coefs.iter_mut().enumerate() {
+      let exp = self.min_exp + i as i64;
+      *v = v.checked_mul(scalar).ok_or(PolyError::Overflow { exp })?;
+    }
+    res.normalize();
+    Ok(res)
+  }
+
+  /// Multiplies the polynomial by t^`k` in place.
+  pub fn shift_exponents(&mut self, k: i64) {
+    if !self.coefs.is_empty() {
+      self.min_exp += k;
+    }
+  }
+
+  /// Adds `coef` * t^`shift` * `other` to the polynomial in place, without
+  /// allocating temporaries. Panics if the polynomials are in different
+  /// variables.
+  pub fn add_scaled_shifted(&mut self, other: &Poly<R>, coef: &R, shift: i64) {
+    self.var = self.expect_common_var(other);
+    if other.coefs.is_empty() || coef.is_zero() {
+      return;
+    }
+    let lo = other.min_exp + shift;
+    self.reserve_exps(lo, other.max_exp() + shift);
+    let offset = (lo - self.min_exp) as usize;
+    for (i, v) in other.coefs.iter().enumerate() {
+      self.coefs[offset + i].add_product(v, coef);
+    }
+    self.normalize();
+  }
+
+  /// Like `add_scaled_shifted`, but fails if the polynomials are in
+  /// different variables, or names the exponent at which the coefficient
+  /// arithmetic overflowed. After an overflow the polynomial holds a partial
+  /// sum.
+  pub fn checked_add_scaled_shifted(&mut self, other: &Poly<R>, coef: &R,
+                                    shift: i64) -> Result<(), PolyError> {
+    self.var = self.common_var(other)?;
+    if other.coefs.is_empty() || coef.is_zero() {
+      return Ok(());
+    }
+    let lo = other.min_exp + shift;
+    self.reserve_exps(lo, other.max_exp() + shift);
+    let offset = (lo - self.min_exp) as usize;
+    let mut res = Ok(());
+    for (i, v) in other.coefs.iter().enumerate() {
+      let c = &mut self.coefs[offset + i];
+      match v.checked_mul(coef).and_then(|prod| c.checked_add(&prod)) {
+        Some(sum) => *c = sum,
+        None => {
+          res = Err(PolyError::Overflow { exp: lo + i as i64 });
+          break;
+        },
       }
-      res.push_str(&v.to_string());
-      res.push_str(" * t^");
-      res.push_str(&k.to_string());
     }
+    self.normalize();
     res
   }
+
+  // Variable of the result of an arithmetic operation on `self` and `rhs`,
+  // the same for both orders of the operands.
+  fn common_var(&self, rhs: &Poly<R>) -> Result<char, PolyError> {
+    match (self.is_constant(), rhs.is_constant()) {
+      _ if self.var == rhs.var => Ok(self.var),
+      (false, false) =>
+        Err(PolyError::VariableMismatch { lhs: self.var, rhs: rhs.var }),
+      (false, true) => Ok(self.var),
+      (true, false) => Ok(rhs.var),
+      (true, true) => Ok('t'),
+    }
+  }
+
+  // Fields compared by `==` and the ordering: the variable of constants is
+  // left out, since they combine with polynomials in any variable.
+  fn cmp_key(&self) -> (i64, &[R], Option<char>) {
+    let var = if self.is_constant() { None } else { Some(self.var) };
+    (self.min_exp, &self.coefs, var)
+  }
+
+  // Like `common_var`, but panics on a mismatch. Used by the operators.
+  fn expect_common_var(&self, rhs: &Poly<R>) -> char {
+    self.common_var(rhs).unwrap_or_else(|err| panic!("{}", err))
+  }
+
+  // Exponent of the last element of `coefs`.
+  fn max_exp(&self) -> i64 {
+    self.min_exp + self.coefs.len() as i64 - 1
+  }
+
+  // Extends `coefs` with zeros so that it covers t^`lo` ... t^`hi`. Leaves the
+  // storage denormalized until the next call to `normalize`.
+  fn reserve_exps(&mut self, lo: i64, hi: i64) {
+    if self.coefs.is_empty() {
+      self.min_exp = lo;
+      self.coefs = vec![R::zero(); (hi - lo + 1) as usize];
+      return;
+    }
+    if hi > self.max_exp() {
+      let len = (hi - self.min_exp + 1) as usize;
+      self.coefs.resize(len, R::zero());
+    }
+    if lo < self.min_exp {
+      let extra = (self.min_exp - lo) as usize;
+      self.coefs.splice(0..0, vec![R::zero(); extra]);
+      self.min_exp = lo;
+    }
+  }
+
+  // Drops zero coefficients from both ends of `coefs`.
+  fn normalize(&mut self) {
+    while self.coefs.last().is_some_and(R::is_zero) {
+      self.coefs.pop();
+    }
+    let leading_zeros = self.coefs.iter().take_while(|v| v.is_zero()).count();
+    if leading_zeros > 0 {
+      self.coefs.drain(0..leading_zeros);
+      self.min_exp += leading_zeros as i64;
+    }
+    if self.coefs.is_empty() {
+      self.min_exp = 0;
+    }
+  }
+}
+
+impl<R: Ring> PartialEq for Poly<R> {
+  fn eq(&self, rhs: &Poly<R>) -> bool {
+    self.cmp_key() == rhs.cmp_key()
+  }
+}
+
+impl<R: Ring> Eq for Poly<R> {}
+
+impl<R: Ring + Hash> Hash for Poly<R> {
+  fn hash<H: Hasher>(&self, state: &mut H) {
+    self.cmp_key().hash(state);
+  }
 }
 
-impl<'a> std::ops::Add for &'a Poly {
-  type Output = Poly;
-  fn add(self, rhs: &'a Poly) -> Poly {
+impl<R: Ring + PartialOrd> PartialOrd for Poly<R> {
+  fn partial_cmp(&self, rhs: &Poly<R>) -> Option<Ordering> {
+    self.cmp_key().partial_cmp(&rhs.cmp_key())
+  }
+}
+
+impl<R: Ring + Ord> Ord for Poly<R> {
+  fn cmp(&self, rhs: &Poly<R>) -> Ordering {
+    self.cmp_key().cmp(&rhs.cmp_key())
+  }
+}
+
+impl<R: Ring> std::ops::Add for &Poly<R> {
+  type Output = Poly<R>;
+  fn add(self, rhs: &Poly<R>) -> Poly<R> {
     let mut res = self.clone();
     res += rhs;
     res
   }
 }
 
-impl<'a> std::ops::AddAssign<&'a Poly> for Poly {
-  fn add_assign(&mut self, rhs: &'a Poly) {
-    for (k, v) in rhs.coef_map.iter() {
-      let current = self.coef_map.get(k).cloned().unwrap_or(0);
-      self.coef_map.insert(*k, current + *v);
+impl<'a, R: Ring> std::ops::AddAssign<&'a Poly<R>> for Poly<R> {
+  fn add_assign(&mut self, rhs: &'a Poly<R>) {
+    self.var = self.expect_common_var(rhs);
+    if rhs.coefs.is_empty() {
+      return;
+    }
+    self.reserve_exps(rhs.min_exp, rhs.max_exp());
+    let offset = (rhs.min_exp - self.min_exp) as usize;
+    for (i, v) in rhs.coefs.iter().enumerate() {
+      self.coefs[offset + i].add_in_place(v);
     }
+    self.normalize();
   }
 }
 
-impl<'a> std::ops::Neg for &'a Poly {
-  type Output = Poly;
-  fn neg(self) -> Poly {
+impl<R: Ring> std::ops::Neg for &Poly<R> {
+  type Output = Poly<R>;
+  fn neg(self) -> Poly<R> {
     let mut res = self.clone();
-    for (_, v) in res.coef_map.iter_mut() {
-      *v = -(*v);
+    for v in res.coefs.iter_mut() {
+      v.neg_in_place();
     }
     res
   }
 }
 
-impl<'a> std::ops::Sub for &'a Poly {
-  type Output = Poly;
-  fn sub(self, rhs: &'a Poly) -> Poly {
+impl<R: Ring> std::ops::Sub for &Poly<R> {
+  type Output = Poly<R>;
+  fn sub(self, rhs: &Poly<R>) -> Poly<R> {
     let mut res = self.clone();
     res -= rhs;
     res
   }
 }
 
-impl<'a> std::ops::SubAssign<&'a Poly> for Poly {
-  fn sub_assign(&mut self, rhs: &'a Poly) {
-    for (k, v) in rhs.coef_map.iter() {
-      let current = self.coef_map.get(k).cloned().unwrap_or(0);
-      self.coef_map.insert(*k, current - *v);
+impl<'a, R: Ring> std::ops::SubAssign<&'a Poly<R>> for Poly<R> {
+  fn sub_assign(&mut self, rhs: &'a Poly<R>) {
+    self.var = self.expect_common_var(rhs);
+    if rhs.coefs.is_empty() {
+      return;
+    }
+    self.reserve_exps(rhs.min_exp, rhs.max_exp());
+    let offset = (rhs.min_exp - self.min_exp) as usize;
+    for (i, v) in rhs.coefs.iter().enumerate() {
+      self.coefs[offset + i].sub_in_place(v);
     }
+    self.normalize();
   }
 }
 
-impl<'a> std::ops::Mul<i64> for &'a Poly {
-  type Output = Poly;
-  fn mul(self, rhs: i64) -> Poly {
+impl<R: Ring> std::ops::Mul<R> for &Poly<R> {
+  type Output = Poly<R>;
+  fn mul(self, rhs: R) -> Poly<R> {
     let mut res = self.clone();
     res *= rhs;
     res
   }
 }
 
-impl std::ops::MulAssign<i64> for Poly {
-  fn mul_assign(&mut self, rhs: i64) {
-    for (_, v) in self.coef_map.iter_mut() {
-      *v *= rhs;
+impl<R: Ring> std::ops::MulAssign<R> for Poly<R> {
+  fn mul_assign(&mut self, rhs: R) {
+    for v in self.coefs.iter_mut() {
+      v.mul_in_place(&rhs);
     }
+    self.normalize();
   }
 }
 
-impl<'a> std::ops::Mul for &'a Poly {
-  type Output = Poly;
-  fn mul(self, rhs: &'a Poly) -> Poly {
-    let mut res = HashMap::new();
-    for (k1, v1) in self.coef_map.iter() {
-      for (k2, v2) in rhs.coef_map.iter() {
-        let ind: i64 = *k1 + *k2;
-        let current = res.get(&ind).cloned().unwrap_or(0);
-        res.insert(ind, current + (*v1) * (*v2));
-      }
+impl<R: Ring> std::ops::Mul for &Poly<R> {
+  type Output = Poly<R>;
+  fn mul(self, rhs: &Poly<R>) -> Poly<R> {
+    let var = self.expect_common_var(rhs);
+    if self.coefs.is_empty() || rhs.coefs.is_empty() {
+      return Poly::zero().with_var(var);
+    }
+    // Multiplication by t, t^-1 and other monomials is a shift.
+    if rhs.is_monomial() {
+      return self.mul_monomial(&rhs.coefs[0], rhs.min_exp).with_var(var);
+    }
+    if self.is_monomial() {
+      return rhs.mul_monomial(&self.coefs[0], self.min_exp).with_var(var);
     }
-    res.shrink_to_fit();
-    Poly { coef_map: res }
+    let coefs = mul::mul_coefs(&self.coefs, &rhs.coefs);
+    Poly::from_coefs(self.min_exp + rhs.min_exp, coefs).with_var(var)
   }
 }
 
-impl<'a> std::ops::MulAssign<&'a Poly> for Poly {
-  fn mul_assign(&mut self, rhs: &'a Poly) {
-    self.coef_map = (&*self * rhs).coef_map;
+impl<'a, R: Ring> std::ops::MulAssign<&'a Poly<R>> for Poly<R> {
+  fn mul_assign(&mut self, rhs: &'a Poly<R>) {
+    *self = &*self * rhs;
   }
 }
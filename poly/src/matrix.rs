@@ -0,0 +1,167 @@
+use std::ops::Mul;
+
+use error::PolyError;
+use ring::Ring;
+use Poly;
+
+/// Dense matrix with Laurent polynomial entries, for representations of braid
+/// groups such as the Burau one. The arithmetic panics when the dimensions do
+/// not match, like `Poly` does on mismatched variables.
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+pub struct PolyMatrix<R: Ring = i64> {
+  rows: usize,
+  cols: usize,
+  // Entries row by row.
+  entries: Vec<Poly<R>>,
+}
+
+impl<R: Ring> PolyMatrix<R> {
+  /// Gives the `rows` x `cols` matrix of zeros.
+  pub fn zero(rows: usize, cols: usize) -> PolyMatrix<R> {
+    PolyMatrix { rows, cols, entries: vec![Poly::zero(); rows * cols] }
+  }
+
+  /// Gives the `n` x `n` identity matrix.
+  pub fn identity(n: usize) -> PolyMatrix<R> {
+    let mut res = PolyMatrix::zero(n, n);
+    for i in 0..n {
+      res.set(i, i, Poly::number(R::one()));
+    }
+    res
+  }
+
+  /// Gives the matrix with the given rows, which must all have the same
+  /// length.
+  pub fn from_rows(rows: Vec<Vec<Poly<R>>>) -> PolyMatrix<R> {
+    let cols = rows.first().map_or(0, Vec::len);
+    assert!(rows.iter().all(|row| row.len() == cols),
+            "matrix rows have different lengths");
+    PolyMatrix { rows: rows.len(), cols, entries: rows.concat() }
+  }
+
+  /// Gives the number of rows.
+  pub fn rows(&self) -> usize {
+    self.rows
+  }
+
+  /// Gives the number of columns.
+  pub fn cols(&self) -> usize {
+    self.cols
+  }
+
+  /// Checks whether the number of rows and columns is the same.
+  pub fn is_square(&self) -> bool {
+    self.rows == self.cols
+  }
+
+  /// Gives the entry in row `i` and column `j`, counting from zero.
+  pub fn get(&self, i: usize, j: usize) -> &Poly<R> {
+    assert!(i < self.rows && j < self.cols, "matrix index out of range");
+    &self.entries[i * self.cols + j]
+  }
+
+  /// Sets the entry in row `i` and column `j`, counting from zero.
+  pub fn set(&mut self, i: usize, j: usize, entry: Poly<R>) {
+    assert!(i < self.rows && j < self.cols, "matrix index out of range");
+    self.entries[i * self.cols + j] = entry;
+  }
+
+  /// Gives the transposed matrix.
+  pub fn transpose(&self) -> PolyMatrix<R> {
+    let mut res = PolyMatrix::zero(self.cols, self.rows);
+    for i in 0..self.rows {
+      for j in 0..self.cols {
+        res.set(j, i, self.get(i, j).clone());
+      }
+    }
+    res
+  }
+
+  /// Gives the determinant of a square matrix by the fraction-free Bareiss
+  /// elimination, where every division is exact. This takes O(n^3)
+  /// polynomial operations, and keeps the entries no larger than minors of
+  /// the original matrix.
+  pub fn determinant(&self) -> Poly<R> {
+    assert!(self.is_square(), "determinant of a non-square matrix");
+    let n = self.rows;
+    let mut a: Vec<Vec<Poly<R>>> = self.entries.chunks(n.max(1))
+      .map(<[Poly<R>]>::to_vec).collect();
+    let mut prev = Poly::number(R::one());
+    let mut negate = false;
+    for k in 0..n {
+      // Swap a row with a nonzero pivot into place.
+      match (k..n).find(|&i| !a[i][k].is_zero()) {
+        None => return Poly::zero(),
+        Some(i) if i != k => {
+          a.swap(i, k);
+          negate = !negate;
+        },
+        Some(_) => (),
+      }
+      for i in k + 1..n {
+        for j in k + 1..n {
+          // By Sylvester's identity, the division is exact.
+          let num = &a[i][j] * &a[k][k] - &a[i][k] * &a[k][j];
+          a[i][j] = num.div_exact(&prev)
+            .expect("Bareiss elimination gives exact divisions");
+        }
+      }
+      prev = a[k][k].clone();
+    }
+    if negate { -prev } else { prev }
+  }
+
+  /// Gives the matrix of cofactors transposed, so that `self * adjugate` is
+  /// the determinant times the identity.
+  pub fn adjugate(&self) -> PolyMatrix<R> {
+    assert!(self.is_square(), "adjugate of a non-square matrix");
+    let n = self.rows;
+    let mut res = PolyMatrix::zero(n, n);
+    for i in 0..n {
+      for j in 0..n {
+        let minor = self.minor(i, j).determinant();
+        res.set(j, i, if (i + j) % 2 == 0 { minor } else { -minor });
+      }
+    }
+    res
+  }
+
+  /// Gives the inverse of a square matrix whose determinant is a unit, that
+  /// is a monomial with an invertible coefficient, as for braid group
+  /// representations. Fails for any other matrix, whose inverse would have
+  /// entries outside of the Laurent polynomials.
+  pub fn inverse(&self) -> Result<PolyMatrix<R>, PolyError> {
+    let det_inverse = self.determinant().pow(-1)?;
+    let mut res = self.adjugate();
+    for entry in res.entries.iter_mut() {
+      *entry *= &det_inverse;
+    }
+    Ok(res)
+  }
+
+  // Gives the matrix without row `i` and column `j`.
+  fn minor(&self, i: usize, j: usize) -> PolyMatrix<R> {
+    let entries = self.entries.iter().enumerate()
+      .filter(|&(ind, _)| ind / self.cols != i && ind % self.cols != j)
+      .map(|(_, entry)| entry.clone())
+      .collect();
+    PolyMatrix { rows: self.rows - 1, cols: self.cols - 1, entries }
+  }
+}
+
+impl<R: Ring> Mul for &PolyMatrix<R> {
+  type Output = PolyMatrix<R>;
+  fn mul(self, rhs: &PolyMatrix<R>) -> PolyMatrix<R> {
+    assert_eq!(self.cols, rhs.rows, "matrix dimensions do not match");
+    let mut res = PolyMatrix::zero(self.rows, rhs.cols);
+    for i in 0..self.rows {
+      for j in 0..rhs.cols {
+        let entry = (0..self.cols)
+          .map(|k| self.get(i, k) * rhs.get(k, j))
+          .sum();
+        res.set(i, j, entry);
+      }
+    }
+    res
+  }
+}
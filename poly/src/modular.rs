@@ -0,0 +1,197 @@
+use std::fmt;
+
+use bigint::BigInt;
+use ring::Ring;
+use Poly;
+
+/// Integers modulo `P`, for computing images of integer polynomials without
+/// overflow. `P` must be below 2^63, and should be prime, so that every
+/// nonzero element is invertible.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub struct Zp<const P: u64> {
+  // Representative in 0 ... P - 1.
+  value: u64,
+}
+
+impl<const P: u64> Zp<P> {
+  /// Gives the residue of `value` modulo `P`.
+  pub fn new(value: u64) -> Zp<P> {
+    Zp { value: value % P }
+  }
+
+  /// Gives the representative of the residue in 0 ... `P` - 1.
+  pub fn value(&self) -> u64 {
+    self.value
+  }
+
+  /// Gives the modulus `P`.
+  pub fn modulus() -> u64 {
+    P
+  }
+}
+
+// Gives the inverse of `a` modulo `m`, or `None` unless they are coprime.
+fn mod_inverse(a: u64, m: u64) -> Option<u64> {
+  // Extended Euclid, keeping only the coefficients of `a`.
+  let (mut r0, mut r1) = (m as i128, (a % m) as i128);
+  let (mut s0, mut s1) = (0_i128, 1_i128);
+  while r1 != 0 {
+    let q = r0 / r1;
+    let r2 = r0 - q * r1;
+    r0 = r1;
+    r1 = r2;
+    let s2 = s0 - q * s1;
+    s0 = s1;
+    s1 = s2;
+  }
+  if r0 != 1 {
+    return None;
+  }
+  Some(s0.rem_euclid(m as i128) as u64)
+}
+
+impl<const P: u64> fmt::Display for Zp<P> {
+  fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+    write!(f, "{}", self.value)
+  }
+}
+
+impl<const P: u64> Ring for Zp<P> {
+  fn zero() -> Zp<P> {
+    Zp { value: 0 }
+  }
+
+  fn one() -> Zp<P> {
+    Zp { value: 1 % P }
+  }
+
+  fn from_i64(num: i64) -> Zp<P> {
+    Zp { value: (num as i128).rem_euclid(P as i128) as u64 }
+  }
+
+  fn is_zero(&self) -> bool {
+    self.value == 0
+  }
+
+  fn add_in_place(&mut self, rhs: &Zp<P>) {
+    self.value += rhs.value;
+    if self.value >= P {
+      self.value -= P;
+    }
+  }
+
+  fn sub_in_place(&mut self, rhs: &Zp<P>) {
+    if self.value < rhs.value {
+      self.value += P;
+    }
+    self.value -= rhs.value;
+  }
+
+  fn mul_in_place(&mut self, rhs: &Zp<P>) {
+    self.value = (self.value as u128 * rhs.value as u128 % P as u128) as u64;
+  }
+
+  fn neg_in_place(&mut self) {
+    if self.value != 0 {
+      self.value = P - self.value;
+    }
+  }
+
+  fn inverse(&self) -> Option<Zp<P>> {
+    mod_inverse(self.value, P).map(|value| Zp { value })
+  }
+}
+
+// Gives the y in 0 ... modulus * m - 1 with y = x (mod `modulus`) and
+// y = r (mod m), where x lies in 0 ... modulus - 1.
+fn crt_step(x: &BigInt, modulus: &BigInt, r: u64, m: u64) -> BigInt {
+  let big_m = BigInt::from(m as i128);
+  let x_mod_m = x.div_rem(&big_m).1.to_i64().unwrap() as u64;
+  let modulus_mod_m = modulus.div_rem(&big_m).1.to_i64().unwrap() as u64;
+  let inv = mod_inverse(modulus_mod_m, m).expect("moduli are not coprime");
+  // y = x + modulus * ((r - x) / modulus mod m).
+  let diff = (r as i128 - x_mod_m as i128).rem_euclid(m as i128) as u128;
+  let k = (diff * inv as u128 % m as u128) as i128;
+  let mut res = x.clone();
+  res.add_product(modulus, &BigInt::from(k));
+  res
+}
+
+// Gives the representative of `x` modulo `modulus` closest to zero, that is
+// the one in -modulus / 2 ... modulus / 2, for x in 0 ... modulus - 1.
+fn symmetric(x: &BigInt, modulus: &BigInt) -> BigInt {
+  let mut twice = x.clone();
+  twice.add_in_place(x);
+  let mut res = x.clone();
+  if twice > *modulus {
+    res.sub_in_place(modulus);
+  }
+  res
+}
+
+/// Reconstructs an integer from its residues modulo pairwise coprime moduli,
+/// given as (residue, modulus) pairs, by the Chinese remainder theorem. Gives
+/// the solution closest to zero, which is the right one if its absolute value
+/// is below half the product of the moduli. Panics unless the moduli are
+/// pairwise coprime.
+pub fn crt(residues: &[(u64, u64)]) -> BigInt {
+  let mut modulus = BigInt::one();
+  let mut res = BigInt::zero();
+  for &(r, m) in residues.iter() {
+    res = crt_step(&res, &modulus, r % m, m);
+    modulus.mul_in_place(&BigInt::from(m as i128));
+  }
+  symmetric(&res, &modulus)
+}
+
+/// Reconstructs a polynomial with integer coefficients from its images in
+/// `Poly<Zp<P>>` for several primes `P`, coefficient by coefficient, by the
+/// Chinese remainder theorem.
+#[derive(Clone, Debug)]
+pub struct CrtPoly {
+  // Coefficients in 0 ... modulus - 1, agreeing with all images so far.
+  poly: Poly<BigInt>,
+  // Product of the moduli of all images so far.
+  modulus: BigInt,
+}
+
+impl CrtPoly {
+  /// Gives the reconstruction from no images at all.
+  pub fn new() -> CrtPoly {
+    CrtPoly { poly: Poly::zero(), modulus: BigInt::one() }
+  }
+
+  /// Adds the image of the polynomial modulo `P`, which must be coprime with
+  /// the moduli of the previous images.
+  pub fn add_image<const P: u64>(&mut self, image: &Poly<Zp<P>>) {
+    let lows = [self.poly.min_degree(), image.min_degree()];
+    let highs = [self.poly.max_degree(), image.max_degree()];
+    let lo = lows.iter().flatten().min().cloned().unwrap_or(0);
+    let hi = highs.iter().flatten().max().cloned().unwrap_or(-1);
+    let coefs = (lo..hi + 1).map(|k| {
+      let r = image.get_coef(k).value();
+      crt_step(&self.poly.get_coef(k), &self.modulus, r, P)
+    }).collect();
+    self.poly = Poly::from_coefs(lo, coefs).with_var(image.var());
+    self.modulus.mul_in_place(&BigInt::from(P as i128));
+  }
+
+  /// Gives the product of the moduli of all images.
+  pub fn modulus(&self) -> &BigInt {
+    &self.modulus
+  }
+
+  /// Gives the polynomial whose coefficients are the solutions closest to
+  /// zero. It is the right one if all coefficients are below half of
+  /// `modulus()` in absolute value.
+  pub fn result(&self) -> Poly<BigInt> {
+    self.poly.map_coefs(|c| symmetric(c, &self.modulus))
+      .with_var(self.poly.var())
+  }
+}
+
+impl Default for CrtPoly {
+  fn default() -> CrtPoly {
+    CrtPoly::new()
+  }
+}
@@ -0,0 +1,113 @@
+use error::PolyError;
+use ring::Ring;
+use Poly;
+
+// Operands shorter than this are multiplied by the schoolbook algorithm,
+// which beats Karatsuba on small inputs thanks to the lower overhead. The
+// crossover depends on the coefficient ring, and `cargo bench` does not
+// resolve it from the noise, so the value is only a rough estimate.
+const KARATSUBA_THRESHOLD: usize = 16;
+
+// Product of the ordinary polynomials `a` and `b` (lowest coefficient first),
+// both nonempty, by the quadratic schoolbook algorithm.
+fn mul_schoolbook<R: Ring>(a: &[R], b: &[R]) -> Vec<R> {
+  let mut res = vec![R::zero(); a.len() + b.len() - 1];
+  for (i, v1) in a.iter().enumerate() {
+    for (j, v2) in b.iter().enumerate() {
+      res[i + j].add_product(v1, v2);
+    }
+  }
+  res
+}
+
+// Adds `b` to `a`, starting from the `offset` element of `a`.
+fn add_at<R: Ring>(a: &mut [R], b: &[R], offset: usize) {
+  for (x, y) in a[offset..].iter_mut().zip(b.iter()) {
+    x.add_in_place(y);
+  }
+}
+
+// Elementwise sum of `a` and `b`, as long as the longer of them.
+fn sum<R: Ring>(a: &[R], b: &[R]) -> Vec<R> {
+  let (long, short) = if a.len() >= b.len() { (a, b) } else { (b, a) };
+  let mut res = long.to_vec();
+  add_at(&mut res, short, 0);
+  res
+}
+
+// Product of the ordinary polynomials `a` and `b` (lowest coefficient first),
+// both nonempty, by the Karatsuba algorithm: with a = a0 + x^h a1 and
+// b = b0 + x^h b1, the middle term a0 b1 + a1 b0 is found as
+// (a0 + a1)(b0 + b1) - a0 b0 - a1 b1, which takes three half-size products
+// instead of four. The sums may overflow where the product does not, so
+// fixed-width rings always take the schoolbook algorithm.
+pub(crate) fn mul_coefs<R: Ring>(a: &[R], b: &[R]) -> Vec<R> {
+  if R::FIXED_WIDTH || a.len() < KARATSUBA_THRESHOLD
+      || b.len() < KARATSUBA_THRESHOLD {
+    return mul_schoolbook(a, b);
+  }
+  let half = a.len().max(b.len()) / 2;
+  let mut res = vec![R::zero(); a.len() + b.len() - 1];
+  if a.len() <= half || b.len() <= half {
+    // Unbalanced operands: only split the longer one.
+    let (long, short) = if a.len() > b.len() { (a, b) } else { (b, a) };
+    let (long0, long1) = long.split_at(half);
+    add_at(&mut res, &mul_coefs(long0, short), 0);
+    add_at(&mut res, &mul_coefs(long1, short), half);
+    return res;
+  }
+  let (a0, a1) = a.split_at(half);
+  let (b0, b1) = b.split_at(half);
+  let low = mul_coefs(a0, b0);
+  let high = mul_coefs(a1, b1);
+  let mut mid = mul_coefs(&sum(a0, a1), &sum(b0, b1));
+  for (x, y) in mid.iter_mut().zip(low.iter()) {
+    x.sub_in_place(y);
+  }
+  for (x, y) in mid.iter_mut().zip(high.iter()) {
+    x.sub_in_place(y);
+  }
+  add_at(&mut res, &low, 0);
+  add_at(&mut res, &mid, half);
+  add_at(&mut res, &high, 2 * half);
+  res
+}
+
+impl<R: Ring> Poly<R> {
+  // Gives `self` * `coef` * t^`exp` by scaling and shifting the coefficients.
+  pub(crate) fn mul_monomial(&self, coef: &R, exp: i64) -> Poly<R> {
+    let mut res = self.clone();
+    if !coef.is_one() {
+      res *= coef.clone();
+    }
+    res.shift_exponents(exp);
+    res
+  }
+
+  // Like `mul_monomial`, but fails if the coefficient arithmetic overflows.
+  pub(crate) fn checked_mul_monomial(&self, coef: &R, exp: i64)
+      -> Result<Poly<R>, PolyError> {
+    let mut res = if coef.is_one() {
+      self.clone()
+    } else {
+      self.checked_scale(coef).map_err(|err| match err {
+        PolyError::Overflow { exp: k } => PolyError::Overflow { exp: k + exp },
+        err => err,
+      })?
+    };
+    res.shift_exponents(exp);
+    Ok(res)
+  }
+
+  /// Gives `self * rhs` by the plain quadratic algorithm, without the fast
+  /// paths of the `*` operator. Useful as a reference in tests and
+  /// benchmarks.
+  pub fn mul_naive(&self, rhs: &Poly<R>) -> Poly<R> {
+    let var = self.expect_common_var(rhs);
+    if self.coefs.is_empty() || rhs.coefs.is_empty() {
+      return Poly::zero().with_var(var);
+    }
+    Poly::from_coefs(self.min_exp + rhs.min_exp,
+                     mul_schoolbook(&self.coefs, &rhs.coefs)).with_var(var)
+  }
+}
@@ -0,0 +1,355 @@
+use std::collections::BTreeMap;
+use std::fmt;
+
+use error::PolyError;
+use ring::{self, Ring};
+use Poly;
+
+/// Laurent polynomial in several named variables, with coefficients in the
+/// ring `R`, for invariants like the HOMFLY-PT polynomial in `a` and `z`. The
+/// storage is always normalized, so structurally equal polynomials are exactly
+/// the equal ones: the variables are exactly those which appear with a nonzero
+/// exponent, in alphabetical order, and there are no zero coefficients. In
+/// particular constants have no variables at all.
+///
+/// Unlike `Poly`, polynomials in different variables combine freely: the
+/// result is a polynomial in all of them.
+#[derive(Clone, Debug, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub struct MultiPoly<R: Ring = i64> {
+  // Names of the variables, sorted.
+  vars: Vec<char>,
+  // Nonzero coefficients by the exponents of `vars`, in the same order.
+  terms: BTreeMap<Vec<i64>, R>,
+}
+
+impl<R: Ring> MultiPoly<R> {
+  /// Gives the P = 0 polynomial.
+  pub fn zero() -> MultiPoly<R> {
+    MultiPoly { vars: Vec::new(), terms: BTreeMap::new() }
+  }
+
+  /// Gives the P = `num` polynomial.
+  pub fn number(num: R) -> MultiPoly<R> {
+    MultiPoly::monomial(num, &[])
+  }
+
+  /// Gives the P(`var`) = `var` polynomial.
+  pub fn variable(var: char) -> MultiPoly<R> {
+    MultiPoly::monomial(R::one(), &[(var, 1)])
+  }
+
+  /// Gives the monomial `coef` * x1^k1 * x2^k2 * ..., where `powers` lists
+  /// the (xi, ki) pairs. Repeated variables have their exponents added up.
+  /// Panics unless every variable is an ASCII letter.
+  pub fn monomial(coef: R, powers: &[(char, i64)]) -> MultiPoly<R> {
+    let mut vars: Vec<char> = powers.iter().map(|&(var, _)| var).collect();
+    assert!(vars.iter().all(char::is_ascii_alphabetic),
+            "MultiPoly variables must be letters");
+    vars.sort();
+    vars.dedup();
+    let mut exps = vec![0; vars.len()];
+    for &(var, exp) in powers.iter() {
+      exps[vars.binary_search(&var).unwrap()] += exp;
+    }
+    let mut terms = BTreeMap::new();
+    terms.insert(exps, coef);
+    let mut res = MultiPoly { vars, terms };
+    res.normalize();
+    res
+  }
+
+  /// Gets the coefficient in front of the monomial x1^k1 * x2^k2 * ...,
+  /// where `powers` lists the (xi, ki) pairs.
+  pub fn get_coef(&self, powers: &[(char, i64)]) -> R {
+    let key = MultiPoly::<R>::monomial(R::one(), powers);
+    match key.terms.keys().next() {
+      Some(exps) if key.vars.iter().all(|v| self.vars.contains(v)) => {
+        let exps = extend_exps(exps, &key.vars, &self.vars);
+        self.terms.get(&exps).cloned().unwrap_or_else(R::zero)
+      },
+      _ => R::zero(),
+    }
+  }
+
+  /// Sets the coefficient in front of the monomial x1^k1 * x2^k2 * ...,
+  /// where `powers` lists the (xi, ki) pairs, to `coef`.
+  pub fn set_coef(&mut self, powers: &[(char, i64)], coef: R) {
+    let mut delta = MultiPoly::monomial(coef, powers);
+    delta -= &MultiPoly::monomial(self.get_coef(powers), powers);
+    *self += &delta;
+  }
+
+  /// Gives the names of the variables, in alphabetical order.
+  pub fn vars(&self) -> &[char] {
+    &self.vars
+  }
+
+  /// Checks whether P = 0.
+  pub fn is_zero(&self) -> bool {
+    self.terms.is_empty()
+  }
+
+  /// Iterates over the nonzero terms as (exponents, coefficient) pairs, where
+  /// the exponents are those of `vars()`, in the same order.
+  pub fn terms(&self) -> impl Iterator<Item = (&[i64], &R)> {
+    self.terms.iter().map(|(exps, c)| (exps.as_slice(), c))
+  }
+
+  /// Gives the lowest exponent of `var`, or `None` for the zero polynomial.
+  pub fn min_degree(&self, var: char) -> Option<i64> {
+    self.degrees(var).min()
+  }
+
+  /// Gives the highest exponent of `var`, or `None` for the zero polynomial.
+  pub fn max_degree(&self, var: char) -> Option<i64> {
+    self.degrees(var).max()
+  }
+
+  /// Gives the polynomial with `var` replaced by its inverse, like
+  /// `Poly::mirror` does for polynomials in one variable.
+  pub fn mirror(&self, var: char) -> MultiPoly<R> {
+    let mut res = self.clone();
+    if let Ok(ind) = self.vars.binary_search(&var) {
+      res.terms = self.terms.iter().map(|(exps, c)| {
+        let mut exps = exps.clone();
+        exps[ind] = -exps[ind];
+        (exps, c.clone())
+      }).collect();
+    }
+    res
+  }
+
+  /// Gives the polynomial with every variable replaced by its inverse.
+  pub fn mirror_all(&self) -> MultiPoly<R> {
+    let terms = self.terms.iter()
+      .map(|(exps, c)| (exps.iter().map(|k| -k).collect(), c.clone()))
+      .collect();
+    MultiPoly { vars: self.vars.clone(), terms }
+  }
+
+  /// Substitutes `value` from the coefficient ring for `var`. Negative powers
+  /// of `value` need its inverse, so they fail unless `value` is a unit.
+  pub fn specialize(&self, var: char, value: &R)
+      -> Result<MultiPoly<R>, PolyError> {
+    let ind = match self.vars.binary_search(&var) {
+      Ok(ind) => ind,
+      Err(_) => return Ok(self.clone()),
+    };
+    let inverse = if self.min_degree(var) >= Some(0) {
+      None
+    } else if value.is_zero() {
+      return Err(PolyError::DivisionByZero);
+    } else {
+      Some(value.inverse().ok_or(PolyError::NotInvertible)?)
+    };
+    let mut res = MultiPoly::zero();
+    for (exps, c) in self.terms.iter() {
+      let base = if exps[ind] >= 0 {
+        value
+      } else {
+        inverse.as_ref().unwrap()
+      };
+      let mut coef = ring::pow(base, exps[ind].unsigned_abs());
+      coef.mul_in_place(c);
+      let powers: Vec<(char, i64)> = self.vars.iter().cloned()
+        .zip(exps.iter().cloned())
+        .filter(|&(v, _)| v != var)
+        .collect();
+      res += &MultiPoly::monomial(coef, &powers);
+    }
+    Ok(res)
+  }
+
+  /// Gives the polynomial as a `Poly` in `var`, or `None` if it depends on
+  /// any other variable.
+  pub fn to_poly(&self, var: char) -> Option<Poly<R>> {
+    if self.vars.iter().any(|&v| v != var) {
+      return None;
+    }
+    let mut res = Poly::zero().with_var(var);
+    for (exps, c) in self.terms.iter() {
+      res.set_coef(exps.first().cloned().unwrap_or(0), c.clone());
+    }
+    Some(res)
+  }
+
+  // Exponents of `var` over all terms.
+  fn degrees(&self, var: char) -> impl Iterator<Item = i64> + '_ {
+    let ind = self.vars.binary_search(&var).ok();
+    self.terms.keys().map(move |exps| ind.map_or(0, |i| exps[i]))
+  }
+
+  // Sorted variables of both `self` and `rhs`.
+  fn union_vars(&self, rhs: &MultiPoly<R>) -> Vec<char> {
+    let mut vars = self.vars.clone();
+    vars.extend(rhs.vars.iter().cloned());
+    vars.sort();
+    vars.dedup();
+    vars
+  }
+
+  // Terms with exponents over `vars`, a sorted superset of `self.vars`.
+  fn terms_over(&self, vars: &[char]) -> BTreeMap<Vec<i64>, R> {
+    self.terms.iter()
+      .map(|(exps, c)| (extend_exps(exps, &self.vars, vars), c.clone()))
+      .collect()
+  }
+
+  // Combines every term of `rhs` with the like term of `self` by `f`.
+  fn combine_in<F: Fn(&mut R, &R)>(&mut self, rhs: &MultiPoly<R>, f: F) {
+    let vars = self.union_vars(rhs);
+    let mut terms = self.terms_over(&vars);
+    for (exps, c) in rhs.terms_over(&vars).into_iter() {
+      f(terms.entry(exps).or_insert_with(R::zero), &c);
+    }
+    self.vars = vars;
+    self.terms = terms;
+    self.normalize();
+  }
+
+  // Drops zero coefficients, then the variables which only appear with zero
+  // exponents.
+  fn normalize(&mut self) {
+    self.terms = std::mem::take(&mut self.terms).into_iter()
+      .filter(|(_, c)| !c.is_zero())
+      .collect();
+    let used: Vec<bool> = (0..self.vars.len())
+      .map(|i| self.terms.keys().any(|exps| exps[i] != 0))
+      .collect();
+    if used.iter().all(|&u| u) {
+      return;
+    }
+    let vars: Vec<char> = self.vars.iter().zip(used.iter())
+      .filter(|&(_, &u)| u).map(|(&v, _)| v).collect();
+    self.terms = std::mem::take(&mut self.terms).into_iter()
+      .map(|(exps, c)| (extend_exps(&exps, &self.vars, &vars), c))
+      .collect();
+    self.vars = vars;
+  }
+}
+
+// Maps the exponents `exps` of the variables `from` to the variables `to`.
+// The variables missing from `to` are dropped, the ones missing from `from`
+// get zero exponents. Both lists are sorted.
+fn extend_exps(exps: &[i64], from: &[char], to: &[char]) -> Vec<i64> {
+  to.iter()
+    .map(|v| from.binary_search(v).map_or(0, |i| exps[i]))
+    .collect()
+}
+
+impl<R: Ring> From<Poly<R>> for MultiPoly<R> {
+  fn from(poly: Poly<R>) -> MultiPoly<R> {
+    let var = poly.var();
+    let mut res = MultiPoly::zero();
+    for (k, c) in poly.terms() {
+      res += &MultiPoly::monomial(c.clone(), &[(var, k)]);
+    }
+    res
+  }
+}
+
+impl<R: Ring> std::ops::Add for &MultiPoly<R> {
+  type Output = MultiPoly<R>;
+  fn add(self, rhs: &MultiPoly<R>) -> MultiPoly<R> {
+    let mut res = self.clone();
+    res += rhs;
+    res
+  }
+}
+
+impl<'a, R: Ring> std::ops::AddAssign<&'a MultiPoly<R>> for MultiPoly<R> {
+  fn add_assign(&mut self, rhs: &'a MultiPoly<R>) {
+    self.combine_in(rhs, R::add_in_place);
+  }
+}
+
+impl<R: Ring> std::ops::Neg for &MultiPoly<R> {
+  type Output = MultiPoly<R>;
+  fn neg(self) -> MultiPoly<R> {
+    let mut res = self.clone();
+    for c in res.terms.values_mut() {
+      c.neg_in_place();
+    }
+    res
+  }
+}
+
+impl<R: Ring> std::ops::Sub for &MultiPoly<R> {
+  type Output = MultiPoly<R>;
+  fn sub(self, rhs: &MultiPoly<R>) -> MultiPoly<R> {
+    let mut res = self.clone();
+    res -= rhs;
+    res
+  }
+}
+
+impl<'a, R: Ring> std::ops::SubAssign<&'a MultiPoly<R>> for MultiPoly<R> {
+  fn sub_assign(&mut self, rhs: &'a MultiPoly<R>) {
+    self.combine_in(rhs, R::sub_in_place);
+  }
+}
+
+impl<R: Ring> std::ops::Mul<R> for &MultiPoly<R> {
+  type Output = MultiPoly<R>;
+  fn mul(self, rhs: R) -> MultiPoly<R> {
+    let mut res = self.clone();
+    res *= rhs;
+    res
+  }
+}
+
+impl<R: Ring> std::ops::MulAssign<R> for MultiPoly<R> {
+  fn mul_assign(&mut self, rhs: R) {
+    for c in self.terms.values_mut() {
+      c.mul_in_place(&rhs);
+    }
+    self.normalize();
+  }
+}
+
+impl<R: Ring> std::ops::Mul for &MultiPoly<R> {
+  type Output = MultiPoly<R>;
+  fn mul(self, rhs: &MultiPoly<R>) -> MultiPoly<R> {
+    let vars = self.union_vars(rhs);
+    let rhs_terms = rhs.terms_over(&vars);
+    let mut terms = BTreeMap::new();
+    for (exps1, c1) in self.terms_over(&vars).iter() {
+      for (exps2, c2) in rhs_terms.iter() {
+        let exps = exps1.iter().zip(exps2.iter())
+          .map(|(a, b)| a + b).collect();
+        terms.entry(exps).or_insert_with(R::zero).add_product(c1, c2);
+      }
+    }
+    let mut res = MultiPoly { vars, terms };
+    res.normalize();
+    res
+  }
+}
+
+impl<'a, R: Ring> std::ops::MulAssign<&'a MultiPoly<R>> for MultiPoly<R> {
+  fn mul_assign(&mut self, rhs: &'a MultiPoly<R>) {
+    *self = &*self * rhs;
+  }
+}
+
+/// Formats the polynomial like `Poly` does, as in
+/// `P(a, z) = 2 * a^2 * z^0  +  1 * a^2 * z^2`.
+impl<R: Ring> fmt::Display for MultiPoly<R> {
+  fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+    let names: Vec<String> = self.vars.iter().map(char::to_string).collect();
+    write!(f, "P({}) = ", names.join(", "))?;
+    let mut first = true;
+    for (exps, c) in self.terms.iter() {
+      if first {
+        first = false;
+      } else {
+        write!(f, "  +  ")?;
+      }
+      write!(f, "{}", c)?;
+      for (var, k) in self.vars.iter().zip(exps.iter()) {
+        write!(f, " * {}^{}", var, k)?;
+      }
+    }
+    Ok(())
+  }
+}
@@ -0,0 +1,182 @@
+use std::iter::{FromIterator, Product, Sum};
+use std::ops::{Add, AddAssign, Mul, MulAssign, Neg, Sub, SubAssign};
+
+use bigint::BigInt;
+use error::PolyError;
+use rational::Rational;
+use ring::{self, Ring};
+use Poly;
+
+// Implements the binary operator for owned and mixed operands on top of the
+// compound assignment with a reference, reusing the storage of the left
+// operand whenever it is owned.
+macro_rules! impl_owned_binop {
+  ($($op:ident, $method:ident, $op_assign:ident, $method_assign:ident;)*) => {$(
+    impl<R: Ring> $op for Poly<R> {
+      type Output = Poly<R>;
+      fn $method(mut self, rhs: Poly<R>) -> Poly<R> {
+        self.$method_assign(&rhs);
+        self
+      }
+    }
+
+    impl<'a, R: Ring> $op<&'a Poly<R>> for Poly<R> {
+      type Output = Poly<R>;
+      fn $method(mut self, rhs: &'a Poly<R>) -> Poly<R> {
+        self.$method_assign(rhs);
+        self
+      }
+    }
+
+    impl<'a, R: Ring> $op<Poly<R>> for &'a Poly<R> {
+      type Output = Poly<R>;
+      fn $method(self, rhs: Poly<R>) -> Poly<R> {
+        self.$method(&rhs)
+      }
+    }
+
+    impl<R: Ring> $op_assign for Poly<R> {
+      fn $method_assign(&mut self, rhs: Poly<R>) {
+        self.$method_assign(&rhs);
+      }
+    }
+  )*}
+}
+
+impl_owned_binop! {
+  Add, add, AddAssign, add_assign;
+  Sub, sub, SubAssign, sub_assign;
+  Mul, mul, MulAssign, mul_assign;
+}
+
+impl<R: Ring> Neg for Poly<R> {
+  type Output = Poly<R>;
+  fn neg(mut self) -> Poly<R> {
+    for v in self.coefs.iter_mut() {
+      v.neg_in_place();
+    }
+    self
+  }
+}
+
+impl<R: Ring> Mul<R> for Poly<R> {
+  type Output = Poly<R>;
+  fn mul(mut self, rhs: R) -> Poly<R> {
+    self *= rhs;
+    self
+  }
+}
+
+// Implements multiplication by a scalar on the left for concrete coefficient
+// rings, since coherence rules out a blanket `impl Mul<Poly<R>> for R`. All of
+// these rings are commutative, so the scalar simply goes to the right.
+macro_rules! impl_scalar_mul {
+  ($($t:ty),*) => {$(
+    impl<'a> Mul<&'a Poly<$t>> for $t {
+      type Output = Poly<$t>;
+      fn mul(self, rhs: &'a Poly<$t>) -> Poly<$t> {
+        rhs * self
+      }
+    }
+
+    impl Mul<Poly<$t>> for $t {
+      type Output = Poly<$t>;
+      fn mul(self, rhs: Poly<$t>) -> Poly<$t> {
+        rhs * self
+      }
+    }
+  )*}
+}
+
+impl_scalar_mul!(i64, i128, BigInt, Rational);
+
+impl<R: Ring> Sum for Poly<R> {
+  fn sum<I: Iterator<Item = Poly<R>>>(iter: I) -> Poly<R> {
+    iter.fold(Poly::zero(), |acc, p| acc + p)
+  }
+}
+
+impl<'a, R: Ring> Sum<&'a Poly<R>> for Poly<R> {
+  fn sum<I: Iterator<Item = &'a Poly<R>>>(iter: I) -> Poly<R> {
+    iter.fold(Poly::zero(), |acc, p| acc + p)
+  }
+}
+
+impl<R: Ring> Product for Poly<R> {
+  fn product<I: Iterator<Item = Poly<R>>>(iter: I) -> Poly<R> {
+    iter.fold(Poly::number(R::one()), |acc, p| acc * p)
+  }
+}
+
+impl<'a, R: Ring> Product<&'a Poly<R>> for Poly<R> {
+  fn product<I: Iterator<Item = &'a Poly<R>>>(iter: I) -> Poly<R> {
+    iter.fold(Poly::number(R::one()), |acc, p| acc * p)
+  }
+}
+
+/// Collects (exponent, coefficient) terms, adding up the coefficients of
+/// repeated exponents.
+impl<R: Ring> FromIterator<(i64, R)> for Poly<R> {
+  fn from_iter<I: IntoIterator<Item = (i64, R)>>(iter: I) -> Poly<R> {
+    let terms: Vec<(i64, R)> = iter.into_iter().collect();
+    let lo = terms.iter().map(|&(k, _)| k).min().unwrap_or(0);
+    let hi = terms.iter().map(|&(k, _)| k).max().unwrap_or(-1);
+    let mut coefs = vec![R::zero(); (hi - lo + 1) as usize];
+    for (k, c) in terms.iter() {
+      coefs[(k - lo) as usize].add_in_place(c);
+    }
+    Poly::from_coefs(lo, coefs)
+  }
+}
+
+impl<R: Ring> Default for Poly<R> {
+  fn default() -> Poly<R> {
+    Poly::zero()
+  }
+}
+
+impl<R: Ring> From<i64> for Poly<R> {
+  fn from(num: i64) -> Poly<R> {
+    Poly::number(R::from_i64(num))
+  }
+}
+
+impl<R: Ring> Poly<R> {
+  /// Gives P^`n`, with P^0 = 1 even for the zero polynomial. Negative powers
+  /// are only defined for monomials with an invertible coefficient. Fails if
+  /// the exponents or the coefficients of the result overflow.
+  pub fn pow(&self, n: i64) -> Result<Poly<R>, PolyError> {
+    if self.is_monomial() {
+      let exp = self.min_exp.checked_mul(n).ok_or(PolyError::ExpOverflow)?;
+      let coef = if n >= 0 {
+        self.coefs[0].clone()
+      } else {
+        self.coefs[0].inverse().ok_or(PolyError::NotInvertible)?
+      };
+      let coef = ring::checked_pow(&coef, n.unsigned_abs())
+        .ok_or(PolyError::Overflow { exp })?;
+      return Ok(Poly::monomial(coef, exp).with_var(self.var));
+    }
+    if n < 0 {
+      return Err(if self.is_zero() {
+        PolyError::DivisionByZero
+      } else {
+        PolyError::NotInvertible
+      });
+    }
+    // Binary exponentiation.
+    let mut res = Poly::number(R::one()).with_var(self.var);
+    let mut base = self.clone();
+    let mut exp = n as u64;
+    while exp > 0 {
+      if exp & 1 == 1 {
+        res = res.checked_mul(&base)?;
+      }
+      exp >>= 1;
+      if exp > 0 {
+        base = base.checked_mul(&base)?;
+      }
+    }
+    Ok(res)
+  }
+}
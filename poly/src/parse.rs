@@ -0,0 +1,364 @@
+use std::collections::BTreeMap;
+use std::error::Error;
+use std::fmt;
+use std::str::FromStr;
+
+use ring::{IntegerRing, Ring};
+use {FracPoly, Poly};
+
+/// Error returned when parsing a `Poly` or `FracPoly` from a malformed string.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct ParsePolyError {
+  /// Byte offset in the input at which parsing failed.
+  pub pos: usize,
+  /// What went wrong at that offset.
+  pub kind: ParsePolyErrorKind,
+}
+
+/// The reason why parsing a `Poly` or `FracPoly` failed.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum ParsePolyErrorKind {
+  /// A character which cannot appear at this position.
+  UnexpectedChar(char),
+  /// The input ended in the middle of a term.
+  UnexpectedEnd,
+  /// A coefficient is not representable in the coefficient ring.
+  CoefOverflow,
+  /// An exponent does not fit in `i64`, or the exponents differ by more than
+  /// 2^24.
+  ExpOverflow,
+  /// An exponent is a fraction other than an integer, which only a
+  /// `FracPoly` accepts.
+  FractionalExp,
+}
+
+impl fmt::Display for ParsePolyError {
+  fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+    match self.kind {
+      ParsePolyErrorKind::UnexpectedChar(c) =>
+        write!(f, "unexpected character '{}' at position {}", c, self.pos),
+      ParsePolyErrorKind::UnexpectedEnd =>
+        write!(f, "unexpected end of input at position {}", self.pos),
+      ParsePolyErrorKind::CoefOverflow =>
+        write!(f, "coefficient out of range at position {}", self.pos),
+      ParsePolyErrorKind::ExpOverflow =>
+        write!(f, "exponent out of range at position {}", self.pos),
+      ParsePolyErrorKind::FractionalExp =>
+        write!(f, "fractional exponent at position {}", self.pos),
+    }
+  }
+}
+
+impl Error for ParsePolyError {}
+
+// Largest difference between the exponents of a parsed polynomial. The
+// coefficients are stored densely, so a short input like `t^10000000000 + 1`
+// would otherwise allocate gigabytes.
+const MAX_EXP_SPAN: i64 = 1 << 24;
+
+// A parsed term: where it starts, its exponent as a numerator and a positive
+// denominator in lowest terms, and its coefficient.
+type Term<R> = (usize, (i64, i64), R);
+
+// Recursive descent parser over the input string. Whitespace is allowed
+// between any two tokens.
+struct Parser<'a> {
+  input: &'a str,
+  pos: usize,
+  // Name of the variable, once it is known from the prefix or the first term.
+  var: Option<char>,
+}
+
+impl<'a> Parser<'a> {
+  fn skip_whitespace(&mut self) {
+    let rest = &self.input[self.pos..];
+    self.pos += rest.len() - rest.trim_start().len();
+  }
+
+  // Next non-whitespace byte, without consuming it.
+  fn peek(&mut self) -> Option<u8> {
+    self.skip_whitespace();
+    self.input.as_bytes().get(self.pos).cloned()
+  }
+
+  fn eat(&mut self, c: u8) -> bool {
+    if self.peek() == Some(c) {
+      self.pos += 1;
+      true
+    } else {
+      false
+    }
+  }
+
+  fn expect(&mut self, c: u8) -> Result<(), ParsePolyError> {
+    if self.eat(c) {
+      Ok(())
+    } else {
+      Err(self.unexpected())
+    }
+  }
+
+  fn error(&self, pos: usize, kind: ParsePolyErrorKind) -> ParsePolyError {
+    ParsePolyError { pos, kind }
+  }
+
+  // Error for whatever is at the current position.
+  fn unexpected(&self) -> ParsePolyError {
+    match self.input[self.pos..].chars().next() {
+      Some(c) => self.error(self.pos, ParsePolyErrorKind::UnexpectedChar(c)),
+      None => self.error(self.pos, ParsePolyErrorKind::UnexpectedEnd),
+    }
+  }
+
+  // Consumes the variable, which is any single letter unless it is already
+  // known. Returns whether there was one.
+  fn variable(&mut self) -> bool {
+    match self.peek() {
+      Some(c) if c.is_ascii_alphabetic() &&
+                 self.var.unwrap_or(c as char) == c as char => {
+        self.pos += 1;
+        self.var = Some(c as char);
+        true
+      },
+      _ => false,
+    }
+  }
+
+  fn expect_variable(&mut self) -> Result<(), ParsePolyError> {
+    if self.variable() {
+      Ok(())
+    } else {
+      Err(self.unexpected())
+    }
+  }
+
+  // Skips the optional `P(t) =` prefix printed by `Display`, which names the
+  // variable. Any single letter is accepted as the name of the polynomial.
+  fn prefix(&mut self) -> Result<(), ParsePolyError> {
+    self.skip_whitespace();
+    let bytes = self.input.as_bytes();
+    let is_name = bytes.get(self.pos).is_some_and(u8::is_ascii_alphabetic) &&
+      self.input[self.pos + 1..].trim_start().starts_with('(');
+    if is_name {
+      self.pos += 1;
+      self.expect(b'(')?;
+      self.expect_variable()?;
+      self.expect(b')')?;
+      self.expect(b'=')?;
+    }
+    Ok(())
+  }
+
+  // Consumes a run of `+` and `-` signs, returns whether the overall sign is
+  // negative, or `None` if there were no signs at all.
+  fn signs(&mut self) -> Option<bool> {
+    let mut res = None;
+    loop {
+      if self.eat(b'+') {
+        res = Some(res.unwrap_or(false));
+      } else if self.eat(b'-') {
+        res = Some(!res.unwrap_or(false));
+      } else {
+        return res;
+      }
+    }
+  }
+
+  fn digits(&mut self) -> Option<&'a str> {
+    self.skip_whitespace();
+    let rest = &self.input[self.pos..];
+    let len = rest.bytes().take_while(u8::is_ascii_digit).count();
+    if len == 0 {
+      return None;
+    }
+    self.pos += len;
+    Some(&rest[..len])
+  }
+
+  // Parses the digits of a coefficient, negated if `negative`. The digits
+  // are accumulated with their sign, so that the most negative value of a
+  // fixed-width ring is accepted.
+  fn coef<R: Ring>(&mut self, negative: bool)
+      -> Result<Option<R>, ParsePolyError> {
+    let start = self.pos;
+    let digits = match self.digits() {
+      Some(digits) => digits,
+      None => return Ok(None),
+    };
+    let ten = R::from_i64(10);
+    let mut res = R::zero();
+    for d in digits.bytes() {
+      let digit = R::from_i64((d - b'0') as i64);
+      res = res.checked_mul(&ten)
+        .and_then(|r| {
+          if negative { r.checked_sub(&digit) } else { r.checked_add(&digit) }
+        })
+        .ok_or_else(|| self.error(start, ParsePolyErrorKind::CoefOverflow))?;
+    }
+    Ok(Some(res))
+  }
+
+  // Parses a signed integer exponent, or the numerator of a fraction.
+  fn exp_int(&mut self) -> Result<i64, ParsePolyError> {
+    let negative = self.signs().unwrap_or(false);
+    self.skip_whitespace();
+    let start = self.pos;
+    let digits = self.digits().ok_or_else(|| self.unexpected())?;
+    let sign = if negative { "-" } else { "" };
+    format!("{}{}", sign, digits).parse()
+      .map_err(|_| self.error(start, ParsePolyErrorKind::ExpOverflow))
+  }
+
+  // Parses the exponent after `^`, either `k`, `{k}` or `(k)`, where `k` may
+  // also be a fraction `p/q` inside the braces or parentheses, as printed for
+  // a `FracPoly`. Returns the numerator and the positive denominator in
+  // lowest terms.
+  fn exp(&mut self) -> Result<(i64, i64), ParsePolyError> {
+    let close = if self.eat(b'{') {
+      Some(b'}')
+    } else if self.eat(b'(') {
+      Some(b')')
+    } else {
+      None
+    };
+    let num = self.exp_int()?;
+    let mut den = 1;
+    if close.is_some() && self.eat(b'/') {
+      self.skip_whitespace();
+      let start = self.pos;
+      let digits = self.digits().ok_or_else(|| self.unexpected())?;
+      den = digits.parse()
+        .map_err(|_| self.error(start, ParsePolyErrorKind::ExpOverflow))?;
+      if den == 0 {
+        return Err(self.error(start, ParsePolyErrorKind::UnexpectedChar('0')));
+      }
+    }
+    if let Some(close) = close {
+      self.expect(close)?;
+    }
+    // The remainder keeps the gcd away from the absolute value of i64::MIN.
+    let factor = den.gcd(&(num % den));
+    Ok((num / factor, den / factor))
+  }
+
+  // Parses a single unsigned term `c`, `c * t^k` or `t^k`, where `* ` and
+  // `^k` are optional, negated if `negative`. Returns the exponent as a
+  // fraction in lowest terms, and the coefficient.
+  fn term<R: Ring>(&mut self, negative: bool)
+      -> Result<((i64, i64), R), ParsePolyError> {
+    let coef = self.coef(negative)?;
+    let has_var = if coef.is_some() && self.eat(b'*') {
+      self.expect_variable()?;
+      true
+    } else {
+      self.variable()
+    };
+    if !has_var {
+      return coef.map(|c| ((0, 1), c)).ok_or_else(|| self.unexpected());
+    }
+    let exp = if self.eat(b'^') { self.exp()? } else { (1, 1) };
+    let one = || if negative { R::from_i64(-1) } else { R::one() };
+    Ok((exp, coef.unwrap_or_else(one)))
+  }
+
+  fn terms<R: Ring>(&mut self) -> Result<Vec<Term<R>>, ParsePolyError> {
+    self.prefix()?;
+    let mut res = Vec::new();
+    let mut first = self.peek().is_some();
+    while first || self.peek().is_some() {
+      let negative = match self.signs() {
+        Some(negative) => negative,
+        None if first => false,
+        None => return Err(self.unexpected()),
+      };
+      first = false;
+      self.skip_whitespace();
+      let start = self.pos;
+      let (exp, coef) = self.term::<R>(negative)?;
+      res.push((start, exp, coef));
+    }
+    Ok(res)
+  }
+
+  // Sums the terms, given with integer exponents, into a polynomial in the
+  // parsed variable.
+  fn sum<R: Ring>(&self, terms: Vec<(usize, i64, R)>)
+      -> Result<Poly<R>, ParsePolyError> {
+    // The sum of the coefficients of each exponent, and where its first term
+    // starts. The terms are summed before building the polynomial, so that
+    // the span of the exponents is known before allocating it.
+    let mut sums: BTreeMap<i64, (R, usize)> = BTreeMap::new();
+    for (start, exp, coef) in terms {
+      let entry = sums.entry(exp).or_insert((R::zero(), start));
+      entry.0 = entry.0.checked_add(&coef)
+        .ok_or_else(|| self.error(start, ParsePolyErrorKind::CoefOverflow))?;
+    }
+    sums.retain(|_, entry| !entry.0.is_zero());
+    let res = match (sums.keys().next(), sums.iter().next_back()) {
+      (Some(&min), Some((&max, &(_, pos)))) => {
+        if max.checked_sub(min).is_none_or(|span| span > MAX_EXP_SPAN) {
+          return Err(self.error(pos, ParsePolyErrorKind::ExpOverflow));
+        }
+        let mut coefs = vec![R::zero(); (max - min + 1) as usize];
+        for (exp, (coef, _)) in sums {
+          coefs[(exp - min) as usize] = coef;
+        }
+        Poly::from_coefs(min, coefs)
+      },
+      _ => Poly::zero(),
+    };
+    Ok(res.with_var(self.var.unwrap_or('t')))
+  }
+
+  fn poly<R: Ring>(&mut self) -> Result<Poly<R>, ParsePolyError> {
+    let mut terms = Vec::new();
+    for (start, (num, den), coef) in self.terms()? {
+      if den != 1 {
+        return Err(self.error(start, ParsePolyErrorKind::FractionalExp));
+      }
+      terms.push((start, num, coef));
+    }
+    self.sum(terms)
+  }
+
+  // Parses the polynomial as P(s) in s = t^(1/denom), where denom is the
+  // least common multiple of the denominators of the exponents.
+  fn frac_poly<R: Ring>(&mut self) -> Result<FracPoly<R>, ParsePolyError> {
+    let terms = self.terms()?;
+    let mut denom: i64 = 1;
+    for &(start, (_, den), _) in terms.iter() {
+      denom = (denom / denom.gcd(&den)).checked_mul(den)
+        .ok_or_else(|| self.error(start, ParsePolyErrorKind::ExpOverflow))?;
+    }
+    let mut scaled = Vec::new();
+    for (start, (num, den), coef) in terms {
+      let exp = num.checked_mul(denom / den)
+        .ok_or_else(|| self.error(start, ParsePolyErrorKind::ExpOverflow))?;
+      scaled.push((start, exp, coef));
+    }
+    Ok(FracPoly::new(self.sum(scaled)?, denom))
+  }
+}
+
+impl<R: Ring> FromStr for Poly<R> {
+  type Err = ParsePolyError;
+
+  /// Parses the output of `to_string` and of `display`, as well as
+  /// human-written input like `t^-2 - 3t + 1` or `-A^{-4} + A^{-3}`. The
+  /// variable may be any letter, and is `t` for constants unless named by
+  /// the `P(A) =` prefix.
+  fn from_str(s: &str) -> Result<Poly<R>, ParsePolyError> {
+    Parser { input: s, pos: 0, var: None }.poly()
+  }
+}
+
+impl<R: Ring> FromStr for FracPoly<R> {
+  type Err = ParsePolyError;
+
+  /// Parses the output of `to_string` and of `display`, where exponents
+  /// may be fractions like `t^(1/2)` or `t^{-3/2}`, as well as anything the
+  /// parser of `Poly` accepts.
+  fn from_str(s: &str) -> Result<FracPoly<R>, ParsePolyError> {
+    Parser { input: s, pos: 0, var: None }.frac_poly()
+  }
+}
@@ -0,0 +1,55 @@
+use ring::Ring;
+use Poly;
+
+/// Deterministic pseudorandom generator of polynomials, for property tests and
+/// benchmarks. It runs the SplitMix64 sequence, which is fast and needs no
+/// external entropy, but is not suitable for cryptography.
+#[derive(Clone, Debug)]
+pub struct PolyRng {
+  state: u64,
+}
+
+impl PolyRng {
+  /// Gives the generator starting from `seed`. Equal seeds give equal
+  /// sequences on every platform.
+  pub fn new(seed: u64) -> PolyRng {
+    PolyRng { state: seed }
+  }
+
+  /// Gives the next pseudorandom 64-bit number.
+  pub fn next_u64(&mut self) -> u64 {
+    self.state = self.state.wrapping_add(0x9e3779b97f4a7c15);
+    let mut z = self.state;
+    z = (z ^ (z >> 30)).wrapping_mul(0xbf58476d1ce4e5b9);
+    z = (z ^ (z >> 27)).wrapping_mul(0x94d049bb133111eb);
+    z ^ (z >> 31)
+  }
+
+  /// Gives a pseudorandom number in `lo` ... `hi`, both inclusive, which
+  /// must not be empty. The bias is negligible for ranges much shorter than
+  /// 2^64.
+  pub fn range(&mut self, lo: i64, hi: i64) -> i64 {
+    assert!(lo <= hi, "empty range");
+    let len = (hi as i128 - lo as i128 + 1) as u128;
+    (lo as i128 + (self.next_u64() as u128 % len) as i128) as i64
+  }
+
+  /// Gives a polynomial with exponents in `min_exp` ... `min_exp + len - 1`
+  /// and coefficients in -`bound` ... `bound`. Its actual degrees are
+  /// narrower when the extreme coefficients come out zero.
+  pub fn poly<R: Ring>(&mut self, min_exp: i64, len: usize, bound: i64)
+      -> Poly<R> {
+    let coefs = (0..len).map(|_| R::from_i64(self.range(-bound, bound)))
+      .collect();
+    Poly::from_coefs(min_exp, coefs)
+  }
+
+  /// Gives a polynomial of random shape: up to `max_len` coefficients in
+  /// -`bound` ... `bound`, starting at an exponent in -`max_len` ... `max_len`.
+  pub fn any_poly<R: Ring>(&mut self, max_len: usize, bound: i64) -> Poly<R> {
+    let max_len = max_len as i64;
+    let min_exp = self.range(-max_len, max_len);
+    let len = self.range(0, max_len) as usize;
+    self.poly(min_exp, len, bound)
+  }
+}
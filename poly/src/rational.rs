@@ -0,0 +1,141 @@
+use std::cmp::Ordering;
+use std::fmt;
+
+use bigint::BigInt;
+use ring::Ring;
+
+/// Exact rational number, always stored in lowest terms with a positive
+/// denominator.
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+pub struct Rational {
+  num: BigInt,
+  den: BigInt,
+}
+
+impl Rational {
+  /// Gives the fraction `num` / `den`. Panics if `den` is zero.
+  pub fn new(num: BigInt, den: BigInt) -> Rational {
+    assert!(!den.is_zero(), "Rational with zero denominator");
+    let mut res = Rational { num, den };
+    res.reduce();
+    res
+  }
+
+  /// Gives the numerator of the fraction in lowest terms.
+  pub fn numer(&self) -> &BigInt {
+    &self.num
+  }
+
+  /// Gives the (positive) denominator of the fraction in lowest terms.
+  pub fn denom(&self) -> &BigInt {
+    &self.den
+  }
+
+  /// Gives the nearest `f64` to the number.
+  pub fn to_f64(&self) -> f64 {
+    self.num.to_f64() / self.den.to_f64()
+  }
+
+  fn reduce(&mut self) {
+    if self.den.is_negative() {
+      self.num.neg_in_place();
+      self.den.neg_in_place();
+    }
+    let gcd = self.num.gcd(&self.den);
+    if !gcd.is_one() {
+      self.num = self.num.div_rem(&gcd).0;
+      self.den = self.den.div_rem(&gcd).0;
+    }
+  }
+}
+
+impl From<i64> for Rational {
+  fn from(num: i64) -> Rational {
+    Rational::from(BigInt::from(num))
+  }
+}
+
+impl From<BigInt> for Rational {
+  fn from(num: BigInt) -> Rational {
+    Rational { num, den: BigInt::one() }
+  }
+}
+
+impl Ord for Rational {
+  fn cmp(&self, rhs: &Rational) -> Ordering {
+    let mut lhs = self.num.clone();
+    lhs.mul_in_place(&rhs.den);
+    let mut rhs_num = rhs.num.clone();
+    rhs_num.mul_in_place(&self.den);
+    lhs.cmp(&rhs_num)
+  }
+}
+
+impl PartialOrd for Rational {
+  fn partial_cmp(&self, rhs: &Rational) -> Option<Ordering> {
+    Some(self.cmp(rhs))
+  }
+}
+
+impl fmt::Display for Rational {
+  fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+    if self.den.is_one() {
+      write!(f, "{}", self.num)
+    } else {
+      write!(f, "{}/{}", self.num, self.den)
+    }
+  }
+}
+
+impl Ring for Rational {
+  fn zero() -> Rational {
+    Rational::from(BigInt::zero())
+  }
+
+  fn one() -> Rational {
+    Rational::from(BigInt::one())
+  }
+
+  fn from_i64(num: i64) -> Rational {
+    Rational::from(num)
+  }
+
+  fn is_zero(&self) -> bool {
+    self.num.is_zero()
+  }
+
+  fn is_negative(&self) -> bool {
+    self.num.is_negative()
+  }
+
+  fn add_in_place(&mut self, rhs: &Rational) {
+    self.num.mul_in_place(&rhs.den);
+    self.num.add_product(&rhs.num, &self.den);
+    self.den.mul_in_place(&rhs.den);
+    self.reduce();
+  }
+
+  fn sub_in_place(&mut self, rhs: &Rational) {
+    let mut neg = rhs.clone();
+    neg.neg_in_place();
+    self.add_in_place(&neg);
+  }
+
+  fn mul_in_place(&mut self, rhs: &Rational) {
+    self.num.mul_in_place(&rhs.num);
+    self.den.mul_in_place(&rhs.den);
+    self.reduce();
+  }
+
+  fn neg_in_place(&mut self) {
+    self.num.neg_in_place();
+  }
+
+  fn inverse(&self) -> Option<Rational> {
+    if self.num.is_zero() {
+      None
+    } else {
+      Some(Rational::new(self.den.clone(), self.num.clone()))
+    }
+  }
+}
@@ -0,0 +1,226 @@
+use std::fmt::{Debug, Display};
+
+use bigint::BigInt;
+
+/// Coefficient ring for `Poly`. All arithmetic goes through references so
+/// that coefficients with heap storage are not cloned more than necessary.
+pub trait Ring: Clone + Debug + Display + Eq {
+  /// Gives the additive identity.
+  fn zero() -> Self;
+
+  /// Gives the multiplicative identity.
+  fn one() -> Self;
+
+  /// Gives the image of the integer `num` in the ring.
+  fn from_i64(num: i64) -> Self;
+
+  /// Checks whether `self` is the additive identity.
+  fn is_zero(&self) -> bool;
+
+  /// Sets `self` to `self + rhs`.
+  fn add_in_place(&mut self, rhs: &Self);
+
+  /// Sets `self` to `self - rhs`.
+  fn sub_in_place(&mut self, rhs: &Self);
+
+  /// Sets `self` to `self * rhs`.
+  fn mul_in_place(&mut self, rhs: &Self);
+
+  /// Sets `self` to `-self`.
+  fn neg_in_place(&mut self);
+
+  /// Whether the elements have a fixed width, so that the arithmetic can
+  /// overflow. Algorithms whose intermediate values may exceed the result,
+  /// like Karatsuba multiplication, are not used for such rings.
+  const FIXED_WIDTH: bool = false;
+
+  /// Checks whether `self` is the multiplicative identity.
+  fn is_one(&self) -> bool {
+    *self == Self::one()
+  }
+
+  /// Checks whether `self` is printed with a leading minus sign. Rings
+  /// without an ordering never are.
+  fn is_negative(&self) -> bool {
+    false
+  }
+
+  /// Gives the multiplicative inverse of `self`, or `None` if `self` is not
+  /// a unit. By default only `1` and `-1` are recognized as units.
+  fn inverse(&self) -> Option<Self> {
+    let mut neg = self.clone();
+    neg.neg_in_place();
+    if self.is_one() || neg.is_one() {
+      Some(self.clone())
+    } else {
+      None
+    }
+  }
+
+  /// Gives `self / rhs` if the division is exact, `None` otherwise. By
+  /// default divides by the inverse of `rhs`.
+  fn div_exact(&self, rhs: &Self) -> Option<Self> {
+    rhs.inverse().map(|inv| {
+      let mut res = self.clone();
+      res.mul_in_place(&inv);
+      res
+    })
+  }
+
+  /// Sets `self` to `self + a * b`.
+  fn add_product(&mut self, a: &Self, b: &Self) {
+    let mut prod = a.clone();
+    prod.mul_in_place(b);
+    self.add_in_place(&prod);
+  }
+
+  /// Gives `self + rhs`, or `None` if the sum is not representable.
+  fn checked_add(&self, rhs: &Self) -> Option<Self> {
+    let mut res = self.clone();
+    res.add_in_place(rhs);
+    Some(res)
+  }
+
+  /// Gives `self - rhs`, or `None` if the difference is not representable.
+  fn checked_sub(&self, rhs: &Self) -> Option<Self> {
+    let mut res = self.clone();
+    res.sub_in_place(rhs);
+    Some(res)
+  }
+
+  /// Gives `self * rhs`, or `None` if the product is not representable.
+  fn checked_mul(&self, rhs: &Self) -> Option<Self> {
+    let mut res = self.clone();
+    res.mul_in_place(rhs);
+    Some(res)
+  }
+}
+
+/// Gives `base` raised to the nonnegative power `exp`, by repeated squaring.
+pub fn pow<R: Ring>(base: &R, exp: u64) -> R {
+  let mut base = base.clone();
+  let mut exp = exp;
+  let mut res = R::one();
+  while exp > 0 {
+    if exp & 1 == 1 {
+      res.mul_in_place(&base);
+    }
+    exp >>= 1;
+    // The last square is not needed, and could overflow.
+    if exp > 0 {
+      let square = base.clone();
+      base.mul_in_place(&square);
+    }
+  }
+  res
+}
+
+/// Like `pow`, but gives `None` if the arithmetic overflows.
+pub fn checked_pow<R: Ring>(base: &R, exp: u64) -> Option<R> {
+  let mut base = base.clone();
+  let mut exp = exp;
+  let mut res = R::one();
+  while exp > 0 {
+    if exp & 1 == 1 {
+      res = res.checked_mul(&base)?;
+    }
+    exp >>= 1;
+    if exp > 0 {
+      base = base.checked_mul(&base)?;
+    }
+  }
+  Some(res)
+}
+
+macro_rules! impl_ring_for_primitive {
+  ($($t:ty),*) => {$(
+    impl Ring for $t {
+      const FIXED_WIDTH: bool = true;
+      fn zero() -> $t { 0 }
+      fn one() -> $t { 1 }
+      fn from_i64(num: i64) -> $t { num as $t }
+      fn is_zero(&self) -> bool { *self == 0 }
+      fn is_negative(&self) -> bool { *self < 0 }
+      fn add_in_place(&mut self, rhs: &$t) { *self += *rhs; }
+      fn sub_in_place(&mut self, rhs: &$t) { *self -= *rhs; }
+      fn mul_in_place(&mut self, rhs: &$t) { *self *= *rhs; }
+      fn neg_in_place(&mut self) { *self = -*self; }
+      fn add_product(&mut self, a: &$t, b: &$t) { *self += *a * *b; }
+      fn div_exact(&self, rhs: &$t) -> Option<$t> {
+        match self.checked_rem(*rhs) {
+          Some(0) => self.checked_div(*rhs),
+          _ => None,
+        }
+      }
+      fn checked_add(&self, rhs: &$t) -> Option<$t> {
+        <$t>::checked_add(*self, *rhs)
+      }
+      fn checked_sub(&self, rhs: &$t) -> Option<$t> {
+        <$t>::checked_sub(*self, *rhs)
+      }
+      fn checked_mul(&self, rhs: &$t) -> Option<$t> {
+        <$t>::checked_mul(*self, *rhs)
+      }
+    }
+  )*}
+}
+
+impl_ring_for_primitive!(i64, i128);
+
+/// Coefficient ring which is a subring of the integers, so that its elements
+/// can be embedded into rationals and floats.
+pub trait IntegerRing: Ring + Ord {
+  /// Gives `self` as an arbitrary-precision integer.
+  fn to_bigint(&self) -> BigInt;
+
+  /// Gives the nearest `f64` to `self`.
+  fn to_f64(&self) -> f64;
+
+  /// Gives the nonnegative greatest common divisor of `self` and `rhs`, or
+  /// `None` if it is not representable, which for a fixed-width integer
+  /// happens only when both are zero or its most negative value.
+  fn checked_gcd(&self, rhs: &Self) -> Option<Self>;
+
+  /// Gives the nonnegative greatest common divisor of `self` and `rhs`.
+  /// Panics if it is not representable.
+  fn gcd(&self, rhs: &Self) -> Self {
+    self.checked_gcd(rhs).expect("gcd out of range")
+  }
+}
+
+macro_rules! impl_integer_ring_for_primitive {
+  ($($t:ty),*) => {$(
+    impl IntegerRing for $t {
+      fn to_bigint(&self) -> BigInt { BigInt::from(*self) }
+      fn to_f64(&self) -> f64 { *self as f64 }
+      fn checked_gcd(&self, rhs: &$t) -> Option<$t> {
+        // The magnitudes are unsigned, as that of the most negative value
+        // only fits there.
+        let (mut a, mut b) = (self.unsigned_abs(), rhs.unsigned_abs());
+        while b != 0 {
+          let rem = a % b;
+          a = b;
+          b = rem;
+        }
+        let res = a as $t;
+        if res < 0 { None } else { Some(res) }
+      }
+    }
+  )*}
+}
+
+impl_integer_ring_for_primitive!(i64, i128);
+
+impl IntegerRing for BigInt {
+  fn to_bigint(&self) -> BigInt {
+    self.clone()
+  }
+
+  fn to_f64(&self) -> f64 {
+    BigInt::to_f64(self)
+  }
+
+  fn checked_gcd(&self, rhs: &BigInt) -> Option<BigInt> {
+    Some(BigInt::gcd(self, rhs))
+  }
+}
@@ -0,0 +1,126 @@
+use complex::Complex;
+use ring::IntegerRing;
+use Poly;
+
+// Upper limit on the number of Aberth–Ehrlich iterations. The method
+// converges cubically to simple roots, so this is only reached for
+// clusters of multiple roots.
+const MAX_ITERATIONS: usize = 1000;
+
+/// Approximate complex root of a polynomial.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct Root {
+  /// Approximate value of the root.
+  pub value: Complex,
+  /// Radius of a disk around `value` containing an exact root. Multiple and
+  /// clustered roots get larger estimates.
+  pub error: f64,
+}
+
+// Evaluates the ordinary polynomial with the given coefficients (lowest
+// first) at `z` by the Horner scheme, together with a bound on the rounding
+// error of the result.
+fn horner(coefs: &[f64], z: Complex) -> (Complex, f64) {
+  let mut res = Complex::default();
+  let mut bound = 0.0;
+  for c in coefs.iter().rev() {
+    res = res * z + Complex::from(*c);
+    bound = bound * z.abs() + res.abs();
+  }
+  (res, 2.0 * f64::EPSILON * bound)
+}
+
+// Gives the ratio p(z) / p'(z) for the ordinary polynomial with the given
+// coefficients (lowest first).
+fn newton_correction(coefs: &[f64], z: Complex) -> Complex {
+  let mut p = Complex::default();
+  let mut dp = Complex::default();
+  for c in coefs.iter().rev() {
+    dp = dp * z + p;
+    p = p * z + Complex::from(*c);
+  }
+  p / dp
+}
+
+// Gives an upper bound on the absolute values of the roots of the ordinary
+// polynomial with the given coefficients (lowest first) by Fujiwara's bound.
+fn root_bound(coefs: &[f64]) -> f64 {
+  let n = coefs.len() - 1;
+  let lead = coefs[n].abs();
+  let mut bound = (coefs[0].abs() / lead / 2.0).powf(1.0 / n as f64);
+  for i in 1..n {
+    bound = bound.max((coefs[n - i].abs() / lead).powf(1.0 / i as f64));
+  }
+  2.0 * bound
+}
+
+// Finds all complex roots of the ordinary polynomial with the given
+// coefficients (lowest first) by the simultaneous Aberth–Ehrlich iteration.
+// The highest coefficient must be nonzero.
+fn aberth(coefs: &[f64]) -> Vec<Root> {
+  let n = coefs.len() - 1;
+  // Initial guesses spread on a circle, off the real axis so that they do not
+  // stall on the symmetry of real polynomials.
+  let radius = root_bound(coefs);
+  let mut z: Vec<Complex> = (0..n)
+    .map(|k| {
+      let theta = 2.0 * std::f64::consts::PI * k as f64 / n as f64 + 0.4;
+      Complex::from_polar(radius, theta)
+    })
+    .collect();
+  // Each approximation stops moving once its step is lost in rounding, or
+  // once the value of the polynomial there is.
+  let mut done = vec![false; n];
+  for _ in 0..MAX_ITERATIONS {
+    for k in 0..n {
+      if done[k] {
+        continue;
+      }
+      let (p, rounding) = horner(coefs, z[k]);
+      if p.abs() <= rounding {
+        done[k] = true;
+        continue;
+      }
+      let w = newton_correction(coefs, z[k]);
+      let repulsion = (0..n).filter(|&j| j != k)
+        .fold(Complex::default(), |acc, j| {
+          acc + Complex::from(1.0) / (z[k] - z[j])
+        });
+      let step = w / (Complex::from(1.0) - w * repulsion);
+      if !(step.re.is_finite() && step.im.is_finite()) {
+        continue;
+      }
+      z[k] = z[k] - step;
+      done[k] = step.abs() <= 4.0 * f64::EPSILON * z[k].abs();
+    }
+    if done.iter().all(|&d| d) {
+      break;
+    }
+  }
+  // A disk of n times the Weierstrass correction p(z_k) / (a_n prod (z_k -
+  // z_j)) around z_k contains a root; the rounding error of p(z_k) is added
+  // to its value.
+  let lead = coefs[n].abs();
+  (0..n)
+    .map(|k| {
+      let (p, rounding) = horner(coefs, z[k]);
+      let dist = (0..n).filter(|&j| j != k)
+        .fold(lead, |acc, j| acc * (z[k] - z[j]).abs());
+      let error = n as f64 * (p.abs() + rounding) / dist;
+      Root { value: z[k], error }
+    })
+    .collect()
+}
+
+impl<R: IntegerRing> Poly<R> {
+  /// Finds the nonzero complex roots numerically, with multiplicities, by
+  /// the Aberth–Ehrlich method on t^-`min_degree` P(t). The zero polynomial
+  /// and the monomials have no such roots.
+  pub fn roots(&self) -> Vec<Root> {
+    if self.coefs.len() < 2 {
+      return Vec::new();
+    }
+    let coefs: Vec<f64> = self.coefs.iter().map(IntegerRing::to_f64).collect();
+    aberth(&coefs)
+  }
+}
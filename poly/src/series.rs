@@ -0,0 +1,145 @@
+use std::fmt;
+use std::ops::{Add, Mul, Neg, Sub};
+
+use error::PolyError;
+use ring::Ring;
+use Poly;
+
+/// Truncated Laurent power series P(t) + O(t^prec): only the coefficients in
+/// front of the powers below the precision `prec` are known. Arithmetic keeps
+/// track of the precision, so every known coefficient of a result is exact.
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+pub struct Series<R: Ring = i64> {
+  // Known terms, all with exponents below `prec`.
+  poly: Poly<R>,
+  prec: i64,
+}
+
+// Gives the terms of `poly` with exponents below `prec`.
+fn truncated<R: Ring>(poly: &Poly<R>, prec: i64) -> Poly<R> {
+  if poly.max_exp() < prec {
+    return poly.clone();
+  }
+  let len = (prec - poly.min_exp).max(0) as usize;
+  Poly::from_coefs(poly.min_exp, poly.coefs[..len].to_vec()).with_var(poly.var)
+}
+
+impl<R: Ring> Series<R> {
+  /// Gives the series `poly` + O(t^`prec`), dropping the terms of `poly` from
+  /// t^`prec` up.
+  pub fn from_poly(poly: &Poly<R>, prec: i64) -> Series<R> {
+    Series { poly: truncated(poly, prec), prec }
+  }
+
+  /// Gives the series O(t^`prec`), with no known nonzero terms.
+  pub fn zero(prec: i64) -> Series<R> {
+    Series { poly: Poly::zero(), prec }
+  }
+
+  /// Gives the known terms as a polynomial.
+  pub fn to_poly(&self) -> Poly<R> {
+    self.poly.clone()
+  }
+
+  /// Gives the exponent of the first unknown term.
+  pub fn prec(&self) -> i64 {
+    self.prec
+  }
+
+  /// Gives the name of the variable.
+  pub fn var(&self) -> char {
+    self.poly.var
+  }
+
+  /// Gives the coefficient in front of t^`exp`, or `None` if it is beyond the
+  /// precision.
+  pub fn get_coef(&self, exp: i64) -> Option<R> {
+    if exp < self.prec { Some(self.poly.get_coef(exp)) } else { None }
+  }
+
+  /// Gives the lowest exponent with a known nonzero coefficient, or `None`
+  /// if all known coefficients are zero.
+  pub fn valuation(&self) -> Option<i64> {
+    self.poly.min_degree()
+  }
+
+  /// Gives the same series with the precision lowered to `prec`, if that is
+  /// below the current one.
+  pub fn truncate(&self, prec: i64) -> Series<R> {
+    Series::from_poly(&self.poly, prec.min(self.prec))
+  }
+
+  /// Gives the inverse series. The lowest known term must be a unit of the
+  /// coefficient ring times a power of t. The relative precision, the number
+  /// of known terms from the lowest nonzero one, stays the same.
+  pub fn inverse(&self) -> Result<Series<R>, PolyError> {
+    let low = self.valuation().ok_or(PolyError::DivisionByZero)?;
+    let lead_inverse = self.poly.get_coef(low).inverse()
+      .ok_or(PolyError::NotInvertible)?;
+    // With P = c t^low (1 + a_1 t + a_2 t^2 + ...) and its inverse
+    // c^-1 t^-low (b_0 + b_1 t + ...), every b_n is determined by the
+    // a_k for k <= n.
+    let len = (self.prec - low) as usize;
+    let mut coefs: Vec<R> = Vec::with_capacity(len);
+    coefs.push(lead_inverse.clone());
+    for n in 1..len {
+      let mut sum = R::zero();
+      for k in 1..n + 1 {
+        sum.add_product(&self.poly.get_coef(low + k as i64), &coefs[n - k]);
+      }
+      sum.mul_in_place(&lead_inverse);
+      sum.neg_in_place();
+      coefs.push(sum);
+    }
+    let poly = Poly::from_coefs(-low, coefs).with_var(self.poly.var);
+    Ok(Series { poly, prec: self.prec - 2 * low })
+  }
+
+  // Gives the lowest exponent which may have a nonzero coefficient.
+  fn order(&self) -> i64 {
+    self.valuation().unwrap_or(self.prec)
+  }
+}
+
+impl<R: Ring> Add for &Series<R> {
+  type Output = Series<R>;
+  fn add(self, rhs: &Series<R>) -> Series<R> {
+    Series::from_poly(&(&self.poly + &rhs.poly), self.prec.min(rhs.prec))
+  }
+}
+
+impl<R: Ring> Sub for &Series<R> {
+  type Output = Series<R>;
+  fn sub(self, rhs: &Series<R>) -> Series<R> {
+    Series::from_poly(&(&self.poly - &rhs.poly), self.prec.min(rhs.prec))
+  }
+}
+
+impl<R: Ring> Neg for &Series<R> {
+  type Output = Series<R>;
+  fn neg(self) -> Series<R> {
+    Series { poly: -&self.poly, prec: self.prec }
+  }
+}
+
+/// Multiplies the known terms, skipping those which only affect the
+/// coefficients beyond the precision of the product.
+impl<R: Ring> Mul for &Series<R> {
+  type Output = Series<R>;
+  fn mul(self, rhs: &Series<R>) -> Series<R> {
+    let prec = (self.prec + rhs.order()).min(rhs.prec + self.order());
+    let lhs_poly = truncated(&self.poly, prec - rhs.order());
+    let rhs_poly = truncated(&rhs.poly, prec - self.order());
+    Series::from_poly(&(&lhs_poly * &rhs_poly), prec)
+  }
+}
+
+impl<R: Ring> fmt::Display for Series<R> {
+  fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+    write!(f, "{}", self.poly)?;
+    if !self.poly.is_zero() {
+      write!(f, "  +  ")?;
+    }
+    write!(f, "O({}^{})", self.poly.var, self.prec)
+  }
+}
@@ -0,0 +1,83 @@
+use error::PolyError;
+use ring::{self, Ring};
+use Poly;
+
+impl<R: Ring> Poly<R> {
+  /// Gives P(t^`k`). For `k` = 0 this is the constant P(1), and for `k` = -1
+  /// this is the mirror polynomial.
+  pub fn substitute_power(&self, k: i64) -> Poly<R> {
+    if self.is_zero() {
+      return self.clone();
+    }
+    if k == 0 {
+      let mut sum = R::zero();
+      for c in self.coefs.iter() {
+        sum.add_in_place(c);
+      }
+      return Poly::number(sum).with_var(self.var);
+    }
+    let step = k.unsigned_abs() as usize;
+    let mut coefs = vec![R::zero(); (self.coefs.len() - 1) * step + 1];
+    for (i, c) in self.coefs.iter().enumerate() {
+      let ind = if k > 0 { i } else { self.coefs.len() - 1 - i };
+      coefs[ind * step] = c.clone();
+    }
+    let min_exp = if k > 0 { self.min_exp * k } else { self.max_exp() * k };
+    Poly { min_exp, coefs, var: self.var }
+  }
+
+  /// Gives P(t^(`num` / `den`)). Fails if some term of P would get a
+  /// non-integer exponent.
+  pub fn substitute_fractional_power(&self, num: i64, den: i64)
+      -> Result<Poly<R>, PolyError> {
+    if den == 0 {
+      return Err(PolyError::DivisionByZero);
+    }
+    if self.terms().any(|(k, _)| (k * num) % den != 0) {
+      return Err(PolyError::InexactSubstitution);
+    }
+    if num == 0 {
+      return Ok(self.substitute_power(0));
+    }
+    let mut res = Poly::zero().with_var(self.var);
+    for (k, c) in self.terms() {
+      res.set_coef(k * num / den, c.clone());
+    }
+    Ok(res)
+  }
+
+  /// Gives P(-t).
+  pub fn negate_variable(&self) -> Poly<R> {
+    let mut res = self.clone();
+    for (i, c) in res.coefs.iter_mut().enumerate() {
+      if (self.min_exp + i as i64) % 2 != 0 {
+        c.neg_in_place();
+      }
+    }
+    res
+  }
+
+  /// Gives P(`coef` * t^`k`). Negative powers of the monomial need `coef` to
+  /// be a unit.
+  pub fn compose_monomial(&self, coef: &R, k: i64)
+      -> Result<Poly<R>, PolyError> {
+    // The term c * t^e of P becomes c * coef^e * t^(k e).
+    let base = if self.min_exp >= 0 {
+      coef.clone()
+    } else if coef.is_zero() {
+      return Err(PolyError::DivisionByZero);
+    } else {
+      coef.inverse().ok_or(PolyError::NotInvertible)?
+    };
+    let mut coef_power = ring::pow(&base, self.min_exp.unsigned_abs());
+    let mut scaled = Vec::with_capacity(self.coefs.len());
+    for c in self.coefs.iter() {
+      let mut value = c.clone();
+      value.mul_in_place(&coef_power);
+      scaled.push(value);
+      coef_power.mul_in_place(coef);
+    }
+    let scaled = Poly::from_coefs(self.min_exp, scaled).with_var(self.var);
+    Ok(scaled.substitute_power(k))
+  }
+}
@@ -0,0 +1,48 @@
+use bigint::BigInt;
+use rational::Rational;
+use ring::Ring;
+use Poly;
+
+impl<R: Ring> Poly<R> {
+  /// Checks whether the coefficients read the same from both ends, that is
+  /// P(t) = t^k P(t^-1) with k = `min_degree + max_degree`. The zero
+  /// polynomial is palindromic.
+  pub fn is_palindromic(&self) -> bool {
+    self.coefs.iter().eq(self.coefs.iter().rev())
+  }
+
+  /// Checks whether the coefficients read from one end are the negated ones
+  /// read from the other, that is P(t) = -t^k P(t^-1) with k =
+  /// `min_degree + max_degree`. The zero polynomial is antipalindromic.
+  pub fn is_antipalindromic(&self) -> bool {
+    self.coefs.iter().zip(self.coefs.iter().rev()).all(|(a, b)| {
+      let mut sum = a.clone();
+      sum.add_in_place(b);
+      sum.is_zero()
+    })
+  }
+
+  /// Gives the unit u t^k, with u = 1 or -1, such that P(t) = u t^k P(t^-1),
+  /// as the pair (u, k). That is, checks whether the polynomial equals its
+  /// mirror up to a unit. The zero polynomial has no such unit.
+  pub fn mirror_unit(&self) -> Option<(R, i64)> {
+    let exp = self.min_exp + self.max_exp();
+    if self.is_zero() {
+      None
+    } else if self.is_palindromic() {
+      Some((R::one(), exp))
+    } else if self.is_antipalindromic() {
+      Some((R::from_i64(-1), exp))
+    } else {
+      None
+    }
+  }
+
+  /// Gives the exponent around which the polynomial is palindromic or
+  /// antipalindromic, which is an integer or a half-integer. Amphichiral
+  /// knots have Jones polynomials symmetric around zero.
+  pub fn symmetric_center(&self) -> Option<Rational> {
+    self.mirror_unit()
+      .map(|(_, exp)| Rational::new(BigInt::from(exp), BigInt::from(2)))
+  }
+}
@@ -1,19 +1,30 @@
-use super::Poly;
+use std::collections::{BTreeSet, HashSet};
+
+use super::{crt, BigInt, Complex, CrtPoly, Cyclotomic, DecodePolyError,
+            FracPoly, IntegerRing, MultiPoly, Notation, ParsePolyError,
+            ParsePolyErrorKind, Poly, PolyError, PolyMatrix, PolyRng, Rational,
+            Ring, Root, Series, Zp};
+
+// Shorthand for parsing polynomials with `i64` coefficients.
+fn parse(s: &str) -> Poly {
+  s.parse().unwrap()
+}
 
 #[test]
 fn literals() {
-  assert_eq!(format!("{:?}", Poly::zero()), "Poly { coef_map: {} }");
-  assert_eq!(format!("{:?}", Poly::number(42)),
-             "Poly { coef_map: {0: 42} }");
-  assert_eq!(format!("{:?}", Poly::identity()),
-             "Poly { coef_map: {1: 1} }");
-  assert_eq!(format!("{:?}", Poly::inverse_identity()),
-             "Poly { coef_map: {-1: 1} }");
+  assert_eq!(format!("{:?}", Poly::<i64>::zero()),
+             "Poly { min_exp: 0, coefs: [], var: 't' }");
+  assert_eq!(format!("{:?}", Poly::number(42_i64)),
+             "Poly { min_exp: 0, coefs: [42], var: 't' }");
+  assert_eq!(format!("{:?}", Poly::<i64>::identity()),
+             "Poly { min_exp: 1, coefs: [1], var: 't' }");
+  assert_eq!(format!("{:?}", Poly::<i64>::inverse_identity()),
+             "Poly { min_exp: -1, coefs: [1], var: 't' }");
 }
 
 #[test]
 fn coef_access() {
-  let mut poly = Poly::number(15);
+  let mut poly: Poly = Poly::number(15);
   assert_eq!(poly.get_coef(0), 15);
   assert_eq!(poly.get_coef(1), 0);
   poly.set_coef(1, 40);
@@ -24,7 +35,7 @@ fn coef_access() {
 
 #[test]
 fn equality() {
-  let mut poly = Poly::zero();
+  let mut poly: Poly = Poly::zero();
   assert_eq!(&poly, &Poly::zero());
   poly.set_coef(-1, 1);
   assert_ne!(&poly, &Poly::zero());
@@ -33,15 +44,68 @@ fn equality() {
   assert_eq!(&poly, &Poly::inverse_identity());
 }
 
+#[test]
+fn normalization() {
+  let mut poly: Poly = Poly::from_coefs(-3, vec![0, 0, 4, 0, 5, 0]);
+  assert_eq!(format!("{:?}", poly),
+             "Poly { min_exp: -1, coefs: [4, 0, 5], var: 't' }");
+  poly.set_coef(7, 0);
+  poly.set_coef(-9, 0);
+  assert_eq!(format!("{:?}", poly),
+             "Poly { min_exp: -1, coefs: [4, 0, 5], var: 't' }");
+  poly.set_coef(-1, 0);
+  assert_eq!(format!("{:?}", poly),
+             "Poly { min_exp: 1, coefs: [5], var: 't' }");
+  poly.set_coef(-2, 3);
+  assert_eq!(format!("{:?}", poly),
+             "Poly { min_exp: -2, coefs: [3, 0, 0, 5], var: 't' }");
+
+  let mut cancelled = poly.clone();
+  cancelled -= &poly;
+  assert_eq!(format!("{:?}", cancelled),
+             "Poly { min_exp: 0, coefs: [], var: 't' }");
+  cancelled += &Poly::identity();
+  cancelled += &-&Poly::identity();
+  assert_eq!(&cancelled, &Poly::zero());
+  assert_eq!(&poly.checked_scale(&0).unwrap(), &Poly::zero());
+  assert_eq!(format!("{:?}", poly.mirror()),
+             "Poly { min_exp: -1, coefs: [5, 0, 0, 3], var: 't' }");
+}
+
+#[test]
+fn hashing_and_ordering() {
+  let mut poly1: Poly = Poly::zero();
+  poly1.set_coef(-2, 3);
+  poly1.set_coef(1, -1);
+  let mut poly2 = poly1.clone();
+  poly2 += &Poly::number(5);
+  poly2 -= &Poly::number(5);
+  poly2.set_coef(4, 0);
+
+  let mut hashed = HashSet::new();
+  hashed.insert(poly1.clone());
+  assert!(hashed.contains(&poly2));
+  assert!(!hashed.contains(&poly1.mirror()));
+
+  let sorted: Vec<Poly> = vec![
+    Poly::identity(), poly1.clone(), Poly::zero(), Poly::number(-1),
+    Poly::inverse_identity(), poly2.clone(), Poly::number(2),
+  ].into_iter().collect::<BTreeSet<_>>().into_iter().collect();
+  assert_eq!(sorted, vec![
+    poly1, Poly::inverse_identity(), Poly::zero(), Poly::number(-1),
+    Poly::number(2), Poly::identity(),
+  ]);
+}
+
 #[test]
 fn arithmetic() {
-  let mut poly1 = Poly::zero();
+  let mut poly1: Poly = Poly::zero();
   poly1 += &Poly::identity();
   poly1 += &Poly::identity();
   poly1 += &(&Poly::inverse_identity() * 2);
   poly1 -= &Poly::number(4);
   
-  let mut poly2 = Poly::zero();
+  let mut poly2: Poly = Poly::zero();
   poly2.set_coef(-1, -1);
   poly2.set_coef(0, 2);
   poly2.set_coef(1, -1);
@@ -52,15 +116,15 @@ fn arithmetic() {
 
 #[test]
 fn multiplication() {
-  let mut poly1 = Poly::zero();
+  let mut poly1: Poly = Poly::zero();
   poly1.set_coef(2, 1);
   poly1.set_coef(0, 1);
   
-  let mut poly2 = Poly::zero();
+  let mut poly2: Poly = Poly::zero();
   poly2.set_coef(2, 1);
   poly2.set_coef(0, -1);
 
-  let mut poly3 = Poly::zero();
+  let mut poly3: Poly = Poly::zero();
   poly3.set_coef(4, 1);
   poly3.set_coef(0, -1);
 
@@ -69,7 +133,7 @@ fn multiplication() {
 
 #[test]
 fn multiplication_advanced() {
-  let mut kauffman_unknot = Poly::zero();
+  let mut kauffman_unknot: Poly = Poly::zero();
   kauffman_unknot.set_coef(-2, -1);
   kauffman_unknot.set_coef(2, -1);
 
@@ -82,19 +146,47 @@ fn multiplication_advanced() {
   let res = &(&Poly::identity() * &kauffman_unknot_3) +
             &(&Poly::inverse_identity() * &kauffman_unknot_2);
 
-  let mut expected = Poly::zero();
+  let mut expected: Poly = Poly::zero();
   expected.set_coef(-1, -1);
   expected.set_coef(3, -2);
   expected.set_coef(7, -1);
   assert_eq!(&res, &expected);
 }
 
+#[test]
+fn degrees() {
+  let poly: Poly = "-2t^-3 + 5 + t^4".parse().unwrap();
+  assert_eq!(poly.min_degree(), Some(-3));
+  assert_eq!(poly.max_degree(), Some(4));
+  assert_eq!(poly.span(), Some(7));
+  assert_eq!(poly.trailing_coef(), Some(&-2));
+  assert_eq!(poly.leading_coef(), Some(&1));
+  assert!(!poly.is_zero());
+  assert!(!poly.is_monomial());
+  assert_eq!(poly.terms().collect::<Vec<_>>(),
+             vec![(-3, &-2), (0, &5), (4, &1)]);
+  assert_eq!(poly.terms().rev().map(|(k, _)| k).collect::<Vec<_>>(),
+             vec![4, 0, -3]);
+
+  let monomial: Poly = "-7 t^-2".parse().unwrap();
+  assert!(monomial.is_monomial());
+  assert_eq!(monomial.span(), Some(0));
+  assert_eq!(monomial.leading_coef(), monomial.trailing_coef());
+
+  let zero: Poly = Poly::zero();
+  assert!(zero.is_zero());
+  assert!(!zero.is_monomial());
+  assert_eq!((zero.min_degree(), zero.max_degree()), (None, None));
+  assert_eq!((zero.span(), zero.leading_coef()), (None, None));
+  assert_eq!(zero.terms().count(), 0);
+}
+
 #[test]
 fn mirror() {
-  assert_eq!(&Poly::zero().mirror(), &Poly::zero());
-  assert_eq!(&Poly::identity().mirror(), &Poly::inverse_identity());
+  assert_eq!(&Poly::<i64>::zero().mirror(), &Poly::zero());
+  assert_eq!(&Poly::<i64>::identity().mirror(), &Poly::inverse_identity());
 
-  let mut poly = Poly::zero();
+  let mut poly: Poly = Poly::zero();
   poly.set_coef(5, -7);
   poly.set_coef(-7, 5);
   let mirr = poly.mirror();
@@ -103,3 +195,1279 @@ fn mirror() {
   assert_eq!(mirr.get_coef(5), 0);
   assert_eq!(mirr.get_coef(7), 5);
 }
+
+#[test]
+fn generic_coefficients() {
+  let mut poly1: Poly<i128> = Poly::zero();
+  poly1.set_coef(-1, 1 << 50);
+  poly1.set_coef(1, 3);
+
+  let mut poly2: Poly<i128> = Poly::zero();
+  poly2.set_coef(1, 1 << 50);
+  poly2.set_coef(-1, 3);
+
+  let mut square: Poly<i128> = Poly::zero();
+  square.set_coef(-2, 3 << 50);
+  square.set_coef(0, (1 << 100) + 9);
+  square.set_coef(2, 3 << 50);
+
+  assert_eq!(&poly1.mirror(), &poly2);
+  assert_eq!(&(&poly1 * &poly2), &square);
+  assert_eq!(&(&(&poly1 + &poly2) - &poly2), &poly1);
+  assert_eq!(poly1.to_string(),
+             "P(t) = 1125899906842624 * t^-1  +  3 * t^1");
+}
+
+#[test]
+fn bigint_arithmetic() {
+  let big: BigInt = "123456789012345678901234567890".parse().unwrap();
+  assert_eq!(big.to_string(), "123456789012345678901234567890");
+  assert_eq!(big.to_i64(), None);
+  assert_eq!("-0".parse::<BigInt>().unwrap(), BigInt::zero());
+  assert!("12a".parse::<BigInt>().is_err());
+  assert!("-".parse::<BigInt>().is_err());
+
+  let mut square = big.clone();
+  square.mul_in_place(&big);
+  assert_eq!(square.to_string(),
+             "15241578753238836750495351562536198787501905199875019052100");
+
+  let mut diff = BigInt::from(i64::MIN);
+  diff.sub_in_place(&BigInt::from(i64::MAX));
+  assert_eq!(diff.to_string(), "-18446744073709551615");
+  diff.add_in_place(&BigInt::from(i64::MAX));
+  assert_eq!(diff.to_i64(), Some(i64::MIN));
+  diff.neg_in_place();
+  assert_eq!(diff.to_string(), "9223372036854775808");
+  assert!(BigInt::from(-3) < BigInt::from(2));
+  assert!(big > BigInt::from(i64::MAX));
+}
+
+#[test]
+fn bigint_division() {
+  let a: BigInt = "-340282366920938463463374607431768211457".parse().unwrap();
+  let b: BigInt = "18446744073709551617".parse().unwrap();
+  let (quot, rem) = a.div_rem(&b);
+  assert_eq!(quot.to_string(), "-18446744073709551615");
+  assert_eq!(rem.to_string(), "-2");
+  let mut back = quot.clone();
+  back.mul_in_place(&b);
+  back.add_in_place(&rem);
+  assert_eq!(back, a);
+
+  let (quot, rem) = BigInt::from(-7).div_rem(&BigInt::from(2));
+  assert_eq!((quot.to_i64(), rem.to_i64()), (Some(-3), Some(-1)));
+  let mut c = b.clone();
+  c.mul_in_place(&BigInt::from(6));
+  let mut d = b.clone();
+  d.mul_in_place(&BigInt::from(-4));
+  let mut expected = b.clone();
+  expected.mul_in_place(&BigInt::from(2));
+  assert_eq!(c.gcd(&d), expected);
+  assert_eq!(BigInt::zero().gcd(&BigInt::from(-5)), BigInt::from(5));
+  assert_eq!(BigInt::from(-3).to_f64(), -3.0);
+}
+
+#[test]
+fn rational_arithmetic() {
+  let half = Rational::new(BigInt::from(2), BigInt::from(-4));
+  assert_eq!(half.to_string(), "-1/2");
+  let mut sum = half.clone();
+  sum.add_in_place(&Rational::new(BigInt::from(5), BigInt::from(6)));
+  assert_eq!(sum.to_string(), "1/3");
+  sum.mul_in_place(&Rational::from(3));
+  assert_eq!(sum, Rational::one());
+  assert_eq!(half.inverse(), Some(Rational::from(-2)));
+  assert_eq!(Rational::zero().inverse(), None);
+  assert!(half < Rational::zero());
+  assert_eq!(half.to_f64(), -0.5);
+}
+
+#[test]
+fn bigint_coefficients() {
+  // (t + t^-1)^128 has the central coefficient C(128, 64) > 2^63.
+  let mut base: Poly<BigInt> = Poly::identity();
+  base += &Poly::inverse_identity();
+  let mut power = base.clone();
+  for _ in 0..7 {
+    power = &power * &power;
+  }
+  assert_eq!(power.get_coef(0).to_string(),
+             "23951146041928082866135587776380551750");
+  assert_eq!(power.get_coef(128), BigInt::one());
+  assert_eq!(power.get_coef(1), BigInt::zero());
+}
+
+#[test]
+fn checked_arithmetic() {
+  let mut poly: Poly = Poly::zero();
+  poly.set_coef(-3, 1 << 20);
+  poly.set_coef(2, i64::MAX);
+
+  assert_eq!(poly.checked_add(&Poly::number(7)).unwrap().get_coef(0), 7);
+  assert_eq!(poly.checked_add(&Poly::identity()).unwrap(),
+             &poly + &Poly::identity());
+  assert_eq!(poly.checked_sub(&poly).unwrap(), Poly::zero());
+  assert_eq!(poly.checked_scale(&-1).unwrap(), -&poly);
+
+  let mut one_more: Poly = Poly::zero();
+  one_more.set_coef(2, 1);
+  assert_eq!(poly.checked_add(&one_more),
+             Err(PolyError::Overflow { exp: 2 }));
+  assert_eq!((-&poly).checked_sub(&one_more).unwrap().get_coef(2), i64::MIN);
+  assert_eq!(poly.checked_scale(&2), Err(PolyError::Overflow { exp: 2 }));
+
+  let mut square_root: Poly = Poly::zero();
+  square_root.set_coef(-3, 1 << 31);
+  assert_eq!(square_root.checked_mul(&square_root).unwrap().get_coef(-6),
+             1 << 62);
+  assert_eq!(poly.checked_mul(&square_root),
+             Err(PolyError::Overflow { exp: -1 }));
+  assert_eq!(PolyError::Overflow { exp: -1 }.to_string(),
+             "coefficient overflow at t^-1");
+
+  let mut big: Poly<BigInt> = Poly::number(BigInt::from(i64::MAX));
+  big = big.checked_mul(&big).unwrap();
+  assert_eq!(big.get_coef(0).to_string(),
+             "85070591730234615847396907784232501249");
+}
+
+#[test]
+fn parsing() {
+  let mut poly: Poly = Poly::zero();
+  poly.set_coef(-2, 1);
+  poly.set_coef(1, -3);
+  poly.set_coef(0, 1);
+  assert_eq!(poly.to_string().parse::<Poly>(), Ok(poly.clone()));
+  assert_eq!("t^-2 - 3t + 1".parse::<Poly>(), Ok(poly.clone()));
+  assert_eq!("1+t^{-2}-3*t".parse::<Poly>(), Ok(poly.clone()));
+  assert_eq!("P(t) = 1 * t^-2  +  -3 * t^1  +  1 * t^0".parse::<Poly>(),
+             Ok(poly.clone()));
+
+  let extreme = Poly::from_coefs(i64::MIN, vec![i64::MIN, 0, i64::MAX]);
+  assert_eq!(extreme.to_string().parse::<Poly>(), Ok(extreme));
+
+  let mut knot: Poly = Poly::zero();
+  knot.set_coef(-4, -1);
+  knot.set_coef(-3, 1);
+  knot.set_coef(-1, 1);
+  assert_eq!("-t^{-4}+t^{-3}+t^{ -1 }".parse::<Poly>(), Ok(knot));
+
+  assert_eq!("P(t) = ".parse::<Poly>(), Ok(Poly::zero()));
+  assert_eq!("0".parse::<Poly>(), Ok(Poly::zero()));
+  assert_eq!("t - t".parse::<Poly>(), Ok(Poly::zero()));
+  assert_eq!("2 t".parse::<Poly>(), Ok(&Poly::identity() * 2));
+
+  let big: Poly<BigInt> =
+    "-123456789012345678901234567890 t^3".parse().unwrap();
+  assert_eq!(big.get_coef(3).to_string(), "-123456789012345678901234567890");
+}
+
+#[test]
+fn parsing_errors() {
+  let error = |pos, kind| Err(ParsePolyError { pos, kind });
+  assert_eq!("t^-2 + 3x".parse::<Poly>(),
+             error(8, ParsePolyErrorKind::UnexpectedChar('x')));
+  assert_eq!("t^".parse::<Poly>(), error(2, ParsePolyErrorKind::UnexpectedEnd));
+  assert_eq!("t^{2".parse::<Poly>(),
+             error(4, ParsePolyErrorKind::UnexpectedEnd));
+  assert_eq!("2 t 3".parse::<Poly>(),
+             error(4, ParsePolyErrorKind::UnexpectedChar('3')));
+  assert_eq!("1 + * t".parse::<Poly>(),
+             error(4, ParsePolyErrorKind::UnexpectedChar('*')));
+  assert_eq!("Q(x) = t".parse::<Poly>(),
+             error(7, ParsePolyErrorKind::UnexpectedChar('t')));
+  assert_eq!("A^2 + t".parse::<Poly>(),
+             error(6, ParsePolyErrorKind::UnexpectedChar('t')));
+  assert_eq!("1 + 99999999999999999999 t".parse::<Poly>(),
+             error(4, ParsePolyErrorKind::CoefOverflow));
+  assert_eq!("t^99999999999999999999".parse::<Poly>(),
+             error(2, ParsePolyErrorKind::ExpOverflow));
+  assert_eq!("- 9223372036854775809 t".parse::<Poly>(),
+             error(2, ParsePolyErrorKind::CoefOverflow));
+  assert_eq!("t^10000000000 + 1".parse::<Poly>(),
+             error(0, ParsePolyErrorKind::ExpOverflow));
+  assert_eq!("t^9223372036854775807 + t^-9223372036854775808"
+               .parse::<Poly>(),
+             error(0, ParsePolyErrorKind::ExpOverflow));
+  assert_eq!("1 + t^16777217".parse::<Poly>(),
+             error(4, ParsePolyErrorKind::ExpOverflow));
+  assert_eq!("1 + t^16777216".parse::<Poly>().map(|p| p.terms().count()),
+             Ok(2));
+  assert_eq!("t^10000000000 - t^10000000000 + 1".parse::<Poly>(),
+             Ok(Poly::number(1)));
+  assert_eq!("t^(1/0)".parse::<FracPoly>(),
+             Err(ParsePolyError {
+               pos: 5,
+               kind: ParsePolyErrorKind::UnexpectedChar('0'),
+             }));
+  assert_eq!("t^1/2".parse::<Poly>(),
+             error(3, ParsePolyErrorKind::UnexpectedChar('/')));
+  assert_eq!("t^(2".parse::<Poly>(),
+             error(4, ParsePolyErrorKind::UnexpectedEnd));
+  assert_eq!("t^(1/9223372036854775807) + t^(1/2)".parse::<FracPoly>(),
+             Err(ParsePolyError {
+               pos: 28,
+               kind: ParsePolyErrorKind::ExpOverflow,
+             }));
+  assert_eq!("t^-2 + 3x".parse::<Poly>().unwrap_err().to_string(),
+             "unexpected character 'x' at position 8");
+}
+
+#[test]
+fn notations() {
+  let poly: Poly = "t^-2 - 3t + 1 - t^4".parse().unwrap();
+  assert_eq!(poly.display(Notation::Plain).to_string(), poly.to_string());
+  assert_eq!(poly.display(Notation::Latex).to_string(),
+             "t^{-2} + 1 - 3t - t^{4}");
+  // The examples in the documentation of `Notation`.
+  let doc: Poly = "t^-2 - 3t + 1".parse().unwrap();
+  assert_eq!(doc.to_string(), "P(t) = 1 * t^-2  +  1 * t^0  +  -3 * t^1");
+  assert_eq!(doc.display(Notation::Latex).to_string(), "t^{-2} + 1 - 3t");
+  assert_eq!(doc.display(Notation::Mathematica).to_string(),
+             "t^-2 + 1 - 3*t");
+  assert_eq!(doc.display(Notation::Sage).to_string(), "t^(-2) + 1 - 3*t");
+  assert_eq!(doc.display(Notation::KnotInfo).to_string(), "t^(-2)+1-3*t");
+  let extreme = Poly::from_coefs(-1, vec![i64::MIN, 0, i64::MIN]);
+  assert_eq!(extreme.display(Notation::Latex).to_string(),
+             "-9223372036854775808t^{-1} - 9223372036854775808t");
+  assert_eq!(extreme.display(Notation::KnotInfo).to_string(),
+             "-9223372036854775808*t^(-1)-9223372036854775808*t");
+  assert_eq!(poly.display(Notation::Mathematica).to_string(),
+             "t^-2 + 1 - 3*t - t^4");
+  assert_eq!(poly.display(Notation::Sage).to_string(),
+             "t^(-2) + 1 - 3*t - t^(4)");
+  assert_eq!(poly.display(Notation::KnotInfo).to_string(),
+             "t^(-2)+1-3*t-t^(4)");
+
+  let knot: Poly = "-t^{-4} + 2t^{-3} - 1".parse().unwrap();
+  assert_eq!(knot.display(Notation::Latex).to_string(),
+             "-t^{-4} + 2t^{-3} - 1");
+  assert_eq!(knot.display(Notation::KnotInfo).to_string(),
+             "-t^(-4)+2*t^(-3)-1");
+
+  let big: Poly<BigInt> = "-t - 10000000000000000000000 t^2".parse().unwrap();
+  assert_eq!(big.display(Notation::Mathematica).to_string(),
+             "-t - 10000000000000000000000*t^2");
+
+  for notation in [Notation::Latex, Notation::Mathematica, Notation::Sage,
+                   Notation::KnotInfo].iter() {
+    assert_eq!(Poly::<i64>::zero().display(*notation).to_string(), "0");
+    assert_eq!(Poly::number(-1_i64).display(*notation).to_string(), "-1");
+  }
+}
+
+#[test]
+fn evaluation() {
+  let trefoil: Poly = "t + t^3 - t^4".parse().unwrap();
+  let figure_eight: Poly = "t^-2 - t^-1 + 1 - t + t^2".parse().unwrap();
+  // V(-1) is the determinant of the knot up to sign.
+  assert_eq!(trefoil.eval(&-1), Ok(-3));
+  assert_eq!(figure_eight.eval(&-1), Ok(5));
+  assert_eq!(figure_eight.eval(&1), Ok(1));
+  assert_eq!(figure_eight.eval(&2), Err(PolyError::NotInvertible));
+  assert_eq!(figure_eight.eval(&0), Err(PolyError::DivisionByZero));
+  assert_eq!(trefoil.eval(&0), Ok(0));
+  assert_eq!(trefoil.eval(&2), Ok(-6));
+  // 3^39 is just below 2^63, and its square is not.
+  assert_eq!(Poly::<i64>::monomial(1, 39).eval(&3),
+             Ok(4052555153018976267));
+  assert_eq!(parse("t^70 + 1").eval(&2), Err(PolyError::Overflow { exp: 7 }));
+  assert_eq!(parse("t^64 + t^-2").eval(&2),
+             Err(PolyError::Overflow { exp: 1 }));
+  assert_eq!(parse("t^70 - 2t^69").eval(&2), Ok(0));
+  assert_eq!(parse("t^40").eval(&3), Err(PolyError::Overflow { exp: 40 }));
+  assert_eq!(Poly::<i64>::monomial(3, 1).pow(39),
+             Ok(Poly::monomial(4052555153018976267, 39)));
+
+  let half = Rational::new(BigInt::from(1), BigInt::from(2));
+  assert_eq!(figure_eight.eval_rational(&half).unwrap().to_string(), "11/4");
+  assert_eq!(figure_eight.eval_rational(&Rational::zero()),
+             Err(PolyError::DivisionByZero));
+  assert_eq!(figure_eight.eval_f64(0.5), Ok(2.75));
+  assert_eq!(figure_eight.eval_f64(0.0), Err(PolyError::DivisionByZero));
+
+  // V(e^(2 pi i / 3)) = 1 for every knot.
+  let omega = Complex::root_of_unity(3, 1);
+  for knot in [&trefoil, &figure_eight].iter() {
+    let value = knot.eval_complex(omega).unwrap();
+    assert!((value - Complex::from(1.0)).abs() < 1e-12);
+  }
+  assert_eq!(figure_eight.eval_complex(Complex::default()),
+             Err(PolyError::DivisionByZero));
+}
+
+#[test]
+fn division() {
+  let poly: Poly = "t^-1 + 2 t^2 + 3 t^4".parse().unwrap();
+  assert_eq!(poly.div_monomial(&1, 2), Ok(parse("t^-3 + 2 + 3t^2")));
+  assert_eq!(poly.div_monomial(&2, 0), Err(PolyError::InexactDivision));
+  assert_eq!(poly.div_monomial(&0, 0), Err(PolyError::DivisionByZero));
+  assert_eq!((&poly * 6).div_monomial(&-3, -1), Ok(parse("-2 - 4t^3 - 6t^5")));
+
+  let divisor: Poly = "t^-1 - t".parse().unwrap();
+  let (quot, rem) = poly.div_rem(&divisor).unwrap();
+  assert_eq!(&(&quot * &divisor) + &rem, poly);
+  assert_eq!((rem.min_degree(), rem.max_degree()), (Some(-1), Some(0)));
+  assert_eq!(poly.div_rem(&Poly::zero()), Err(PolyError::DivisionByZero));
+  assert_eq!(poly.div_rem(&parse("2t + 1")),
+             Err(PolyError::NotInvertible));
+  assert_eq!(poly.div_rem(&parse("t^9")),
+             Ok((parse("t^-10 + 2t^-7 + 3t^-5"), Poly::zero())));
+
+  // Kauffman brackets of the 3-braid closures are multiples of the unknot.
+  let unknot: Poly = "-t^-2 - t^2".parse().unwrap();
+  let bracket: Poly = "-t^-6 - 3t^-2 - 3t^2 - t^6".parse().unwrap();
+  assert_eq!(bracket.div_exact(&unknot), Ok(parse("t^-4 + 2 + t^4")));
+  assert_eq!(bracket.div_exact(&parse("2t + 2")),
+             Err(PolyError::InexactDivision));
+  assert_eq!((&bracket * 2).div_exact(&parse("2t^2 + 2t^-2")),
+             Ok(parse("-t^-4 - 2 - t^4")));
+  assert_eq!(poly.div_exact(&unknot), Err(PolyError::InexactDivision));
+}
+
+#[test]
+fn content_and_gcd() {
+  let poly: Poly = "-6t^-1 + 4 - 10 t^3".parse().unwrap();
+  assert_eq!(poly.content(), Some(2));
+  assert_eq!(poly.primitive_part(), parse("-3t^-1 + 2 - 5 t^3"));
+  assert_eq!(Poly::<i64>::zero().content(), Some(0));
+
+  let common: Poly = "1 - t + t^2".parse().unwrap();
+  let a = &(&common * &parse("3t^-2 + 3t")) * 2;
+  let b = &common * &parse("-4t^5 + 6t^3");
+  assert_eq!(a.gcd(&b), Ok(&common * 2));
+  assert_eq!(b.gcd(&a), Ok(&common * 2));
+  assert_eq!(a.gcd(&Poly::zero()),
+             Ok(parse("6 - 6t + 6t^2 + 6t^3 - 6t^4 + 6t^5")));
+  assert_eq!(a.gcd(&parse("t + 2")), Ok(Poly::number(1)));
+  assert_eq!(Poly::<i64>::zero().gcd(&Poly::zero()), Ok(Poly::zero()));
+  assert_eq!(a.gcd(&parse("x + 2")),
+             Err(PolyError::VariableMismatch { lhs: 't', rhs: 'x' }));
+
+  let big_a: Poly<BigInt> = a.map_coefs(|c| BigInt::from(*c));
+  let big_b: Poly<BigInt> = b.map_coefs(|c| BigInt::from(*c));
+  assert_eq!(big_a.gcd(&big_b),
+             Ok(common.map_coefs(|c| BigInt::from(2 * c))));
+
+  // The remainder sequence of these coprime polynomials outgrows i64.
+  let (c, d) = (parse("3t^60 + 1"), parse("5t^2 + 7"));
+  assert_eq!(c.gcd(&d), Err(PolyError::Overflow { exp: 16 }));
+  let big_c: Poly<BigInt> = c.map_coefs(|c| BigInt::from(*c));
+  let big_d: Poly<BigInt> = d.map_coefs(|c| BigInt::from(*c));
+  assert_eq!(big_c.gcd(&big_d), Ok(Poly::number(BigInt::from(1))));
+
+  // The content of the most negative coefficient alone is out of range.
+  assert_eq!(i64::MIN.checked_gcd(&0), None);
+  assert_eq!(IntegerRing::gcd(&i64::MIN, &-6), 2);
+  let min = Poly::monomial(i64::MIN, 3);
+  assert_eq!(min.content(), None);
+  assert_eq!(min.primitive_part(), parse("-t^3"));
+  assert_eq!(Poly::from_coefs(0, vec![i64::MIN, 6]).content(), Some(2));
+  assert_eq!(Poly::from_coefs(0, vec![i64::MIN, i64::MIN]).primitive_part(),
+             parse("-1 - t"));
+  assert_eq!(min.gcd(&Poly::number(6)), Ok(Poly::number(2)));
+  assert_eq!(min.gcd(&parse("t")), Ok(Poly::number(1)));
+  assert_eq!(min.gcd(&min), Err(PolyError::Overflow { exp: 0 }));
+}
+
+#[test]
+fn substitution() {
+  let poly = parse("2t^-1 + 3 - t^2");
+  assert_eq!(poly.substitute_power(2), parse("2t^-2 + 3 - t^4"));
+  assert_eq!(poly.substitute_power(-3), parse("2t^3 + 3 - t^-6"));
+  assert_eq!(poly.substitute_power(-1), poly.mirror());
+  assert_eq!(poly.substitute_power(0), Poly::number(4));
+  assert_eq!(Poly::<i64>::zero().substitute_power(5), Poly::zero());
+
+  let squares = parse("t^-4 - 2 + 5t^6");
+  assert_eq!(squares.substitute_fractional_power(1, 2),
+             Ok(parse("t^-2 - 2 + 5t^3")));
+  assert_eq!(squares.substitute_fractional_power(-3, 2),
+             Ok(parse("t^6 - 2 + 5t^-9")));
+  assert_eq!(squares.substitute_fractional_power(1, 4),
+             Err(PolyError::InexactSubstitution));
+  assert_eq!(squares.substitute_fractional_power(0, 7), Ok(Poly::number(4)));
+  assert_eq!(squares.substitute_fractional_power(1, 0),
+             Err(PolyError::DivisionByZero));
+
+  assert_eq!(poly.negate_variable(), parse("-2t^-1 + 3 - t^2"));
+  assert_eq!(poly.negate_variable().negate_variable(), poly);
+
+  // Writhe normalization of the Kauffman bracket: t^w -> (-t^-3)^w.
+  assert_eq!(Poly::monomial(1, 3).compose_monomial(&-1, -3),
+             Ok(parse("-t^-9")));
+  assert_eq!(poly.compose_monomial(&-1, 1), Ok(poly.negate_variable()));
+  assert_eq!(poly.compose_monomial(&1, -1), Ok(poly.mirror()));
+  assert_eq!(parse("1 + t + t^3").compose_monomial(&2, 2),
+             Ok(parse("1 + 2t^2 + 8t^6")));
+  assert_eq!(poly.compose_monomial(&2, 1), Err(PolyError::NotInvertible));
+  assert_eq!(poly.compose_monomial(&0, 1), Err(PolyError::DivisionByZero));
+  assert_eq!(poly.compose_monomial(&-1, 0), Ok(Poly::number(0)));
+}
+
+#[test]
+fn fractional_exponents() {
+  // Exponents are kept over the smallest common denominator.
+  let sqrt = FracPoly::new(parse("-t^2 - t^10"), 4);
+  assert_eq!(sqrt.denom(), 2);
+  assert_eq!(sqrt.numer_poly(), &parse("-t - t^5"));
+  assert_eq!(sqrt.get_coef(1, 2), -1);
+  assert_eq!(sqrt.get_coef(5, 2), -1);
+  assert_eq!(sqrt.get_coef(1, 1), 0);
+  assert_eq!(sqrt.to_poly(), None);
+  assert_eq!(sqrt.to_denom(6), Some(parse("-t^3 - t^15")));
+  assert_eq!(sqrt.to_denom(3), None);
+  assert_eq!(FracPoly::new(parse("t^-3 + 2t^6"), 3).to_poly(),
+             Some(parse("t^-1 + 2t^2")));
+  assert_eq!(FracPoly::new(Poly::<i64>::zero(), 8).denom(), 1);
+
+  let third = FracPoly::new(parse("1 + t"), 3);
+  let sum = &sqrt + &third;
+  assert_eq!(sum.denom(), 6);
+  assert_eq!(sum, FracPoly::new(parse("1 + t^2 - t^3 - t^15"), 6));
+  assert_eq!(&sum - &third, sqrt);
+  assert_eq!(sum.checked_sub(&sqrt), Ok(third.clone()));
+  // (-t^(1/2)) * (-t^(1/2)) is integral again.
+  let half = FracPoly::new(parse("-t"), 2);
+  assert_eq!(&half * &half, FracPoly::from(parse("t")));
+  assert_eq!(half.checked_mul(&half), Ok(FracPoly::from(parse("t"))));
+  assert_eq!(-&half, FracPoly::new(parse("t"), 2));
+  assert_eq!(sqrt.mirror(), FracPoly::new(parse("-t^-1 - t^-5"), 2));
+
+  assert_eq!(sqrt.to_string(), "P(t) = -1 * t^(1/2)  +  -1 * t^(5/2)");
+  assert_eq!(sum.display(Notation::KnotInfo).to_string(),
+             "1+t^(1/3)-t^(1/2)-t^(5/2)");
+  assert_eq!(sum.display(Notation::Latex).to_string(),
+             "1 + t^{1/3} - t^{1/2} - t^{5/2}");
+  assert_eq!(FracPoly::new(parse("t^-2 + 3t^-1"), 2)
+               .display(Notation::Mathematica).to_string(),
+             "t^-1 + 3*t^(-1/2)");
+
+  // Fractional exponents are parsed from every notation.
+  assert_eq!(sqrt.to_string().parse(), Ok(sqrt.clone()));
+  assert_eq!("1 + t^{1/3} - t^{1/2} - t^{5/2}".parse(), Ok(sum.clone()));
+  assert_eq!("1+t^(2/6)-t^( 1 / 2 )-t^(5/2)".parse(), Ok(sum));
+  assert_eq!("t^(4/2) + t^(-1/1)".parse::<FracPoly>(),
+             Ok(FracPoly::from(parse("t^2 + t^-1"))));
+  assert_eq!("t^(1/2)".parse::<Poly>(), Err(ParsePolyError {
+    pos: 0,
+    kind: ParsePolyErrorKind::FractionalExp,
+  }));
+}
+
+#[test]
+fn variables() {
+  let bracket: Poly = parse("-A^-2 - A^2");
+  assert_eq!(bracket.var(), 'A');
+  assert_eq!(bracket, parse("-t^-2 - t^2").with_var('A'));
+  assert_ne!(bracket, parse("-t^-2 - t^2"));
+  assert_eq!(bracket.to_string(), "P(A) = -1 * A^-2  +  -1 * A^2");
+  assert_eq!(parse(&bracket.to_string()), bracket);
+  assert_eq!(parse("P(q) = 1").var(), 'q');
+  assert_eq!(parse("7").var(), 't');
+  assert_eq!(bracket.display(Notation::Latex).to_string(),
+             "-A^{-2} - A^{2}");
+  assert_eq!(parse("3x + x^-1").display(Notation::KnotInfo).to_string(),
+             "x^(-1)+3*x");
+  assert_eq!(FracPoly::new(parse("-q - q^5"), 2).to_string(),
+             "P(q) = -1 * q^(1/2)  +  -1 * q^(5/2)");
+
+  // Operations on a single polynomial keep its variable.
+  assert_eq!(bracket.mirror().var(), 'A');
+  assert_eq!(bracket.substitute_power(0).var(), 'A');
+  assert_eq!(bracket.compose_monomial(&-1, -3).unwrap().var(), 'A');
+  assert_eq!(bracket.div_monomial(&-1, 2).unwrap().var(), 'A');
+
+  // Constants combine with polynomials in any variable.
+  let two: Poly = Poly::number(2);
+  assert!(two.is_constant() && Poly::<i64>::zero().is_constant());
+  assert!(!bracket.is_constant());
+  assert_eq!(&bracket * &two, parse("-2A^-2 - 2A^2"));
+  assert_eq!(&two - &bracket, parse("2 + A^-2 + A^2"));
+  assert_eq!(bracket.checked_add(&Poly::zero()), Ok(bracket.clone()));
+
+  let jones: Poly = parse("t + t^3 - t^4");
+  let mismatch = PolyError::VariableMismatch { lhs: 'A', rhs: 't' };
+  assert_eq!(bracket.checked_add(&jones), Err(mismatch.clone()));
+  assert_eq!(bracket.checked_sub(&jones), Err(mismatch.clone()));
+  assert_eq!(bracket.checked_mul(&jones), Err(mismatch.clone()));
+  assert_eq!(bracket.div_exact(&jones), Err(mismatch.clone()));
+  assert_eq!(mismatch.to_string(),
+             "polynomials in different variables A and t");
+  let (quot, rem) = jones.div_rem(&Poly::identity()).unwrap();
+  assert_eq!((quot.var(), rem.var()), ('t', 't'));
+}
+
+#[test]
+#[should_panic(expected = "polynomials in different variables A and t")]
+fn variable_mismatch_panics() {
+  let _ = &parse("A^2") + &parse("t");
+}
+
+#[test]
+fn multivariate() {
+  let a: MultiPoly = MultiPoly::variable('a');
+  let z: MultiPoly = MultiPoly::variable('z');
+  let one = MultiPoly::number(1);
+  // HOMFLY-PT polynomial of the right-handed trefoil.
+  let homfly = &(&(&a * &a) * 2) - &MultiPoly::monomial(1, &[('a', 4)]);
+  let homfly = &homfly + &MultiPoly::monomial(1, &[('z', 2), ('a', 2)]);
+  assert_eq!(homfly.vars(), &['a', 'z']);
+  assert_eq!(homfly.get_coef(&[('a', 2)]), 2);
+  assert_eq!(homfly.get_coef(&[('z', 2), ('a', 2)]), 1);
+  assert_eq!(homfly.get_coef(&[('a', 2), ('q', 1)]), 0);
+  assert_eq!(homfly.terms().count(), 3);
+  assert_eq!((homfly.min_degree('a'), homfly.max_degree('a')),
+             (Some(2), Some(4)));
+  assert_eq!((homfly.min_degree('z'), homfly.max_degree('q')),
+             (Some(0), Some(0)));
+  assert_eq!(MultiPoly::<i64>::zero().min_degree('a'), None);
+  assert_eq!(homfly.to_string(),
+             "P(a, z) = 2 * a^2 * z^0  +  1 * a^2 * z^2  +  -1 * a^4 * z^0");
+
+  // Normalization drops cancelled terms and unused variables.
+  let mut sum = &homfly + &MultiPoly::monomial(-1, &[('a', 2), ('z', 2)]);
+  assert_eq!(sum.vars(), &['a']);
+  sum.set_coef(&[('a', 4)], 0);
+  sum.set_coef(&[('b', -1), ('a', 2)], 3);
+  let expected = MultiPoly::monomial(3, &[('a', 2), ('b', -1)]);
+  assert_eq!(sum, &(&(&a * &a) * 2) + &expected);
+  assert_eq!(&(&a - &a) + &one, one);
+  assert_eq!(MultiPoly::<i64>::monomial(5, &[('x', 2), ('x', -2)]),
+             MultiPoly::number(5));
+  assert!((&z - &z).is_zero());
+  assert_eq!(-&(&a + &z), &(&a * -1) - &z);
+  let mut prod = &a + &z;
+  prod *= &(&a - &z);
+  assert_eq!(prod, &(&a * &a) - &(&z * &z));
+  prod -= &one;
+  prod += &one;
+  prod *= 3;
+  assert_eq!(prod.get_coef(&[('z', 2)]), -3);
+
+  // Inversions per variable.
+  let mixed: MultiPoly = MultiPoly::monomial(2, &[('a', 3), ('z', -1)]);
+  assert_eq!(mixed.mirror('a'), MultiPoly::monomial(2, &[('a', -3),
+                                                        ('z', -1)]));
+  assert_eq!(mixed.mirror('z'), MultiPoly::monomial(2, &[('a', 3),
+                                                        ('z', 1)]));
+  assert_eq!(mixed.mirror('q'), mixed);
+  assert_eq!(mixed.mirror_all(), mixed.mirror('a').mirror('z'));
+
+  // Specialization: a = 1 gives the Conway polynomial 1 + z^2.
+  let conway = homfly.specialize('a', &1).unwrap();
+  assert_eq!(conway.to_poly('z'), Some(parse("1 + z^2")));
+  assert_eq!(homfly.to_poly('z'), None);
+  assert_eq!(homfly.specialize('z', &0).unwrap().to_poly('a'),
+             Some(parse("2a^2 - a^4")));
+  assert_eq!(mixed.specialize('z', &-1).unwrap(),
+             MultiPoly::monomial(-2, &[('a', 3)]));
+  assert_eq!(mixed.specialize('z', &2), Err(PolyError::NotInvertible));
+  assert_eq!(mixed.specialize('z', &0), Err(PolyError::DivisionByZero));
+  assert_eq!(one.to_poly('t'), Some(Poly::number(1)));
+
+  let poly: Poly = parse("3q^-2 + q");
+  let lifted = MultiPoly::from(poly.clone());
+  assert_eq!(lifted.vars(), &['q']);
+  assert_eq!(lifted.to_poly('q'), Some(poly));
+}
+
+#[test]
+fn fast_multiplication() {
+  // Both operands long enough for Karatsuba, balanced or not, which `i64`
+  // never takes, but `Zp` and `BigInt` do.
+  for &(len1, len2) in [(16, 16), (15, 40), (64, 64), (130, 65), (200, 70),
+                        (65, 500), (63, 300), (257, 255)].iter() {
+    let mut rng = PolyRng::new(len1 as u64 * 1000 + len2 as u64);
+    let a: Poly = rng.poly(-7, len1, 49);
+    let b: Poly = rng.poly(3, len2, 49);
+    assert_eq!(&a * &b, a.mul_naive(&b));
+    assert_eq!(&b * &a, a.mul_naive(&b));
+    assert_eq!(a.checked_mul(&b), Ok(a.mul_naive(&b)));
+    let big_a = a.map_coefs(|&c| {
+      let mut big = BigInt::from(c << 40);
+      big.mul_in_place(&BigInt::from(c << 40));
+      big
+    });
+    let big_b = b.map_coefs(|&c| BigInt::from(c));
+    assert_eq!(&big_a * &big_b, big_a.mul_naive(&big_b));
+    let zp_a: Poly<Zp<1000003>> = a.map_coefs(|&c| Zp::from_i64(c << 40));
+    let zp_b = b.map_coefs(|&c| Zp::from_i64(c));
+    assert_eq!(&zp_a * &zp_b, zp_a.mul_naive(&zp_b));
+  }
+  // The product fits in `i64`, although the sums Karatsuba would take of
+  // the coefficients of `a` do not.
+  let a = Poly::from_coefs(0, vec![1_i64 << 61; 128]);
+  let b = Poly::from_coefs(0, (0..128).map(|i| 1 - 2 * (i % 2)).collect());
+  assert_eq!(&a * &b, a.mul_naive(&b));
+
+  // Monomials only shift and scale.
+  let poly: Poly = PolyRng::new(5).poly(-20, 50, 49);
+  let shifted = &poly * &Poly::monomial(-3, 4);
+  assert_eq!(shifted, poly.mul_naive(&Poly::monomial(-3, 4)));
+  assert_eq!(&Poly::inverse_identity() * &poly,
+             poly.mul_naive(&Poly::inverse_identity()));
+  assert_eq!(poly.checked_mul(&Poly::identity()),
+             Ok(poly.mul_naive(&Poly::identity())));
+  assert_eq!(&poly * &Poly::zero(), Poly::zero());
+  let huge: Poly = Poly::monomial(1 << 62, 5);
+  assert_eq!(huge.checked_mul(&Poly::monomial(4, -2)),
+             Err(PolyError::Overflow { exp: 3 }));
+  assert_eq!(Poly::monomial(4, -2).checked_mul(&huge),
+             Err(PolyError::Overflow { exp: 3 }));
+}
+
+#[test]
+fn in_place_shifts() {
+  let mut poly = parse("2t^-1 + 3 - t^2");
+  poly.shift_exponents(3);
+  assert_eq!(poly, parse("2t^2 + 3t^3 - t^5"));
+  poly.shift_exponents(-3);
+  let mut zero: Poly = Poly::zero();
+  zero.shift_exponents(5);
+  assert_eq!(zero, Poly::zero());
+
+  // A^s x + A^-s y, and a term which cancels out completely.
+  let mut sum = poly.clone();
+  sum.add_scaled_shifted(&parse("1 + t"), &2, -2);
+  assert_eq!(sum, &poly + &parse("2t^-2 + 2t^-1"));
+  sum.add_scaled_shifted(&poly, &-1, 0);
+  assert_eq!(sum, parse("2t^-2 + 2t^-1"));
+  sum.add_scaled_shifted(&parse("t^-2 + t^-1"), &-2, 0);
+  assert_eq!(sum, Poly::zero());
+  sum.add_scaled_shifted(&poly, &0, 7);
+  assert_eq!(sum, Poly::zero());
+  sum.add_scaled_shifted(&poly, &1, 10);
+  assert_eq!(sum, parse("2t^9 + 3t^10 - t^12"));
+
+  let mut checked = poly.clone();
+  assert_eq!(checked.checked_add_scaled_shifted(&poly, &-1, 0), Ok(()));
+  assert!(checked.is_zero());
+  // The partial sum is kept after an overflow.
+  let mut big: Poly = parse("-t + 5t^2");
+  assert_eq!(big.checked_add_scaled_shifted(&parse("1 + t"), &i64::MAX, 1),
+             Err(PolyError::Overflow { exp: 2 }));
+  assert_eq!(big, Poly::from_coefs(1, vec![i64::MAX - 1, 5]));
+  let mut in_a: Poly = parse("A");
+  assert_eq!(in_a.checked_add_scaled_shifted(&poly, &1, 0),
+             Err(PolyError::VariableMismatch { lhs: 'A', rhs: 't' }));
+  in_a.checked_add_scaled_shifted(&Poly::number(1), &1, 0).unwrap();
+  assert_eq!(in_a, parse("1 + A"));
+}
+
+#[test]
+fn modular_arithmetic() {
+  type Z7 = Zp<7>;
+  let three = Z7::from_i64(3);
+  assert_eq!(Z7::from_i64(-4), three);
+  assert_eq!(Z7::new(24), three);
+  assert_eq!(Z7::modulus(), 7);
+  let mut x = three;
+  x.add_in_place(&Z7::from_i64(5));
+  assert_eq!(x.value(), 1);
+  x.sub_in_place(&three);
+  assert_eq!(x.value(), 5);
+  x.mul_in_place(&three);
+  assert_eq!(x.value(), 1);
+  x.neg_in_place();
+  assert_eq!(x.to_string(), "6");
+  assert_eq!(three.inverse(), Some(Z7::from_i64(5)));
+  assert_eq!(Z7::zero().inverse(), None);
+  assert_eq!(Zp::<12>::from_i64(4).inverse(), None);
+  assert_eq!(Zp::<12>::from_i64(5).inverse(), Some(Zp::new(5)));
+
+  // Multiplication must not overflow for word-size primes.
+  const BIG: u64 = 4611686018427387847;
+  let mut y = Zp::<BIG>::from_i64(-1);
+  y.mul_in_place(&Zp::from_i64(-1));
+  assert!(y.is_one());
+  let poly: Poly<Zp<7>> = parse("3t^-1 + 6t").map_coefs(|&c| Zp::from_i64(c));
+  assert_eq!(poly.eval(&Zp::from_i64(2)), Ok(Zp::new(3)));
+  assert_eq!(poly.to_string(), "P(t) = 3 * t^-1  +  6 * t^1");
+}
+
+#[test]
+fn chinese_remainders() {
+  assert_eq!(crt(&[]), BigInt::zero());
+  assert_eq!(crt(&[(2, 3), (3, 5), (2, 7)]), BigInt::from(23));
+  assert_eq!(crt(&[(1, 3), (2, 5), (5, 7)]), BigInt::from(-23));
+  assert_eq!(crt(&[(12, 5)]), BigInt::from(2));
+
+  // (1 - 2t)^100 has coefficients far beyond i64, and needs three primes.
+  const P1: u64 = 4611686018427387847;
+  const P2: u64 = 4611686018427387817;
+  const P3: u64 = 4611686018427387787;
+  let base: Poly = parse("1 - 2t");
+  let mut exact = Poly::number(BigInt::one());
+  let mut image1 = Poly::number(Zp::<P1>::one());
+  let mut image2 = Poly::number(Zp::<P2>::one());
+  let mut image3 = Poly::number(Zp::<P3>::one());
+  for _ in 0..100 {
+    exact *= &base.map_coefs(|&c| BigInt::from(c));
+    image1 *= &base.map_coefs(|&c| Zp::from_i64(c));
+    image2 *= &base.map_coefs(|&c| Zp::from_i64(c));
+    image3 *= &base.map_coefs(|&c| Zp::from_i64(c));
+  }
+  let mut reconstruction = CrtPoly::new();
+  reconstruction.add_image(&image1);
+  reconstruction.add_image(&image2);
+  assert_ne!(reconstruction.result(), exact);
+  reconstruction.add_image(&image3);
+  assert_eq!(reconstruction.result(), exact);
+  let mut modulus = BigInt::from(P1 as i128);
+  modulus.mul_in_place(&BigInt::from(P2 as i128));
+  modulus.mul_in_place(&BigInt::from(P3 as i128));
+  assert_eq!(reconstruction.modulus(), &modulus);
+
+  let mut in_a = CrtPoly::new();
+  in_a.add_image(&parse("-A^-3 + 2").map_coefs(|&c| Zp::<P1>::from_i64(c)));
+  assert_eq!(in_a.result(), parse("-A^-3 + 2").map_coefs(|&c| BigInt::from(c)));
+}
+
+#[test]
+fn owned_operators() {
+  let x = parse("1 + 2t");
+  let y = parse("t^-1 - t");
+  assert_eq!(x.clone() + y.clone(), &x + &y);
+  assert_eq!(x.clone() + &y, &x + &y);
+  assert_eq!(&x + y.clone(), &x + &y);
+  assert_eq!(x.clone() - y.clone(), &x - &y);
+  assert_eq!(&x - y.clone(), &x - &y);
+  assert_eq!(x.clone() * y.clone(), &x * &y);
+  assert_eq!(&x * y.clone(), &x * &y);
+  assert_eq!(-x.clone(), -&x);
+  assert_eq!(x.clone() * 3, &x * 3);
+  assert_eq!(3 * &x, &x * 3);
+  assert_eq!(3 * x.clone(), parse("3 + 6t"));
+  assert_eq!(BigInt::from(2) * &x.map_coefs(|&c| BigInt::from(c)),
+             parse("2 + 4t").map_coefs(|&c| BigInt::from(c)));
+  let mut z = x.clone();
+  z += y.clone();
+  z -= x.clone();
+  assert_eq!(z, y);
+  z *= y.clone();
+  assert_eq!(z, parse("t^-2 - 2 + t^2"));
+}
+
+#[test]
+fn sums_and_products() {
+  let factors = vec![parse("1 + t"), parse("1 - t"), parse("t^-1")];
+  assert_eq!(factors.iter().sum::<Poly>(), parse("2 + t^-1"));
+  assert_eq!(factors.iter().product::<Poly>(), parse("t^-1 - t"));
+  assert_eq!(factors.into_iter().product::<Poly>(), parse("t^-1 - t"));
+  assert_eq!(Vec::<Poly>::new().into_iter().sum::<Poly>(), Poly::zero());
+  assert_eq!(Vec::<Poly>::new().into_iter().product::<Poly>(),
+             Poly::number(1));
+  let collected: Poly = vec![(2, 1), (-1, 3), (2, 4), (0, 0)]
+    .into_iter().collect();
+  assert_eq!(collected, parse("3t^-1 + 5t^2"));
+  let cancelled: Poly = vec![(1, 1), (1, -1)].into_iter().collect();
+  assert_eq!(cancelled, Poly::zero());
+  assert_eq!(Poly::<i64>::default(), Poly::zero());
+  assert_eq!(Poly::<BigInt>::from(-5), Poly::number(BigInt::from(-5)));
+}
+
+#[test]
+fn powers() {
+  let x = parse("1 - t");
+  assert_eq!(x.pow(0), Ok(Poly::number(1)));
+  assert_eq!(x.pow(1), Ok(x.clone()));
+  assert_eq!(x.pow(5), Ok(parse("1 - 5t + 10t^2 - 10t^3 + 5t^4 - t^5")));
+  assert_eq!(x.pow(-1), Err(PolyError::NotInvertible));
+  assert_eq!(Poly::<i64>::zero().pow(0), Ok(Poly::number(1)));
+  assert_eq!(Poly::<i64>::zero().pow(3), Ok(Poly::zero()));
+  assert_eq!(Poly::<i64>::zero().pow(-1), Err(PolyError::DivisionByZero));
+  assert_eq!(parse("-t^2").pow(-3), Ok(parse("-t^-6")));
+  assert_eq!(parse("2t").pow(3), Ok(parse("8t^3")));
+  assert_eq!(parse("2t").pow(-1), Err(PolyError::NotInvertible));
+  assert_eq!(Poly::<i64>::monomial(1, 1 << 40).pow(1 << 30),
+             Err(PolyError::ExpOverflow));
+  assert_eq!(parse("2t").pow(63), Err(PolyError::Overflow { exp: 63 }));
+  assert_eq!(parse("1 + t").pow(70), Err(PolyError::Overflow { exp: 30 }));
+  let half = Rational::new(BigInt::from(1), BigInt::from(2));
+  let half = Poly::monomial(half, 1).with_var('q');
+  assert_eq!(half.pow(-2),
+             Ok(Poly::monomial(Rational::from(4), -2).with_var('q')));
+}
+
+// Reduced Burau representation of the generators of B3.
+fn burau_generators() -> (PolyMatrix, PolyMatrix) {
+  let sigma1 = PolyMatrix::from_rows(vec![
+    vec![parse("-t"), parse("1")],
+    vec![parse("0"), parse("1")],
+  ]);
+  let sigma2 = PolyMatrix::from_rows(vec![
+    vec![parse("1"), parse("0")],
+    vec![parse("t"), parse("-t")],
+  ]);
+  (sigma1, sigma2)
+}
+
+#[test]
+fn matrices() {
+  let (s1, s2) = burau_generators();
+  assert_eq!((s1.rows(), s1.cols()), (2, 2));
+  assert_eq!(s1.get(0, 0), &parse("-t"));
+  assert_eq!(&s1 * &PolyMatrix::identity(2), s1);
+  // The braid relation.
+  assert_eq!(&(&s1 * &s2) * &s1, &(&s2 * &s1) * &s2);
+  assert_eq!(s1.determinant(), parse("-t"));
+  let s1_inv = s1.inverse().unwrap();
+  assert_eq!(s1_inv.get(0, 0), &parse("-t^-1"));
+  assert_eq!(&s1 * &s1_inv, PolyMatrix::identity(2));
+  assert_eq!(&s2.inverse().unwrap() * &s2, PolyMatrix::identity(2));
+  assert_eq!(s1.transpose().get(0, 1), &parse("0"));
+  let rect = PolyMatrix::from_rows(vec![vec![parse("1"), parse("t")]]);
+  assert_eq!((&rect.transpose() * &rect).determinant(), Poly::zero());
+  let singular = PolyMatrix::from_rows(vec![
+    vec![parse("1 + t"), parse("2")],
+    vec![parse("0"), parse("1 - t")],
+  ]);
+  assert_eq!(singular.inverse(), Err(PolyError::NotInvertible));
+  assert_eq!(PolyMatrix::<i64>::zero(2, 2).inverse(),
+             Err(PolyError::DivisionByZero));
+  assert_eq!(PolyMatrix::<i64>::identity(0).determinant(), Poly::number(1));
+}
+
+#[test]
+fn bareiss_determinant() {
+  // Needs a row swap: the top left entry is zero.
+  let m = PolyMatrix::from_rows(vec![
+    vec![parse("0"), parse("1"), parse("t")],
+    vec![parse("t^-1"), parse("2"), parse("0")],
+    vec![parse("1"), parse("0"), parse("1 + t")],
+  ]);
+  // Cofactor expansion along the first row.
+  assert_eq!(m.determinant(), parse("-t^-1 - 1 - 2t"));
+  assert_eq!(&m * &m.adjugate(),
+             &PolyMatrix::identity(3) * &scalar_matrix(3, m.determinant()));
+  // Alexander polynomial of the trefoil, the closure of s1^3 s2, from the
+  // reduced Burau representation: det(I - B) = (1 + t + t^2) Delta(t).
+  let (s1, s2) = burau_generators();
+  let braid = &(&(&s1 * &s1) * &s1) * &s2;
+  let mut diff = PolyMatrix::identity(2);
+  for i in 0..2 {
+    for j in 0..2 {
+      diff.set(i, j, diff.get(i, j) - braid.get(i, j));
+    }
+  }
+  assert_eq!(diff.determinant().div_exact(&parse("1 + t + t^2")),
+             Ok(parse("1 - t + t^2")));
+}
+
+// Gives `value` times the `n` x `n` identity matrix.
+fn scalar_matrix(n: usize, value: Poly) -> PolyMatrix {
+  let mut res = PolyMatrix::zero(n, n);
+  for i in 0..n {
+    res.set(i, i, value.clone());
+  }
+  res
+}
+
+// Checks that every root is within its error estimate of one of `expected`,
+// which lists the exact roots with multiplicities.
+fn assert_roots(roots: &[Root], expected: &[Complex]) {
+  assert_eq!(roots.len(), expected.len());
+  for root in roots.iter() {
+    assert!(expected.iter().any(|&z| (root.value - z).abs() <= root.error),
+            "{} is not within {} of a root", root.value, root.error);
+  }
+  for &z in expected.iter() {
+    assert!(roots.iter().any(|root| (root.value - z).abs() < 1e-6),
+            "root {} not found", z);
+  }
+}
+
+#[test]
+fn numerical_roots() {
+  assert_eq!(Poly::<i64>::zero().roots(), vec![]);
+  assert_eq!(parse("5t^3").roots(), vec![]);
+  let simple = parse("t^-1 - 3 + 2t").roots();
+  assert_roots(&simple, &[Complex::from(0.5), Complex::from(1.0)]);
+  assert!(simple.iter().all(|root| root.error < 1e-12));
+  let unity: Vec<Complex> =
+    (0..5).map(|k| Complex::root_of_unity(10, 2 * k + 1)).collect();
+  assert_roots(&parse("1 + t^5").map_coefs(|&c| BigInt::from(c)).roots(),
+               &unity);
+  // Double root: the estimates are larger, but still honest.
+  let double = parse("1 + 2t + t^2").roots();
+  assert_roots(&double, &[Complex::from(-1.0), Complex::from(-1.0)]);
+  // Jones polynomial of the trefoil, t + t^3 - t^4.
+  let trefoil = parse("t + t^3 - t^4");
+  for root in trefoil.roots().iter() {
+    assert!(trefoil.eval_complex(root.value).unwrap().abs() < 1e-12);
+    assert!(root.error < 1e-12);
+  }
+}
+
+#[test]
+fn cyclotomic_polynomials() {
+  assert_eq!(Poly::<i64>::cyclotomic(1), parse("t - 1"));
+  assert_eq!(Poly::<i64>::cyclotomic(2), parse("t + 1"));
+  assert_eq!(Poly::<i64>::cyclotomic(6), parse("t^2 - t + 1"));
+  assert_eq!(Poly::<i64>::cyclotomic(12), parse("t^4 - t^2 + 1"));
+  assert_eq!(Poly::<i64>::cyclotomic(9), parse("t^6 + t^3 + 1"));
+  // The product over all divisors is t^n - 1.
+  let product: Poly = [1, 2, 3, 5, 6, 10, 15, 30].iter()
+    .map(|&d| Poly::cyclotomic(d)).product();
+  assert_eq!(product, parse("t^30 - 1"));
+}
+
+#[test]
+fn cyclotomic_fields() {
+  let z = Cyclotomic::zeta(5);
+  let one = Cyclotomic::rational(5, Rational::from(1));
+  let mut power = one.clone();
+  let mut sum = Cyclotomic::rational(5, Rational::from(0));
+  for _ in 0..5 {
+    sum = &sum + &power;
+    power = &power * &z;
+  }
+  assert_eq!(power, one);
+  assert!(sum.is_zero());
+  assert_eq!(z.to_string(), "z");
+  assert_eq!(z.conj().to_string(), "-1 - z - z^(2) - z^(3)");
+  assert_eq!(&z * &z.conj(), one);
+  let x = &(&z * &z) - &Cyclotomic::rational(5, Rational::from(3));
+  assert_eq!(&x * &x.inverse().unwrap(), one);
+  assert_eq!(Cyclotomic::rational(5, Rational::from(0)).inverse(), None);
+  assert_eq!(x.to_rational(), None);
+  // Golden ratio: z + z^-1 = (sqrt(5) - 1) / 2.
+  let golden = &z + &z.conj();
+  assert!((golden.to_complex().re - (5f64.sqrt() - 1.0) / 2.0).abs() < 1e-15);
+  assert_eq!(&golden * &golden, &one - &golden);
+}
+
+#[test]
+fn roots_of_unity() {
+  // Jones polynomial of the trefoil, t + t^3 - t^4.
+  let trefoil = parse("t + t^3 - t^4");
+  assert_eq!(trefoil.eval_at_root_of_unity(2, 1).to_rational(),
+             Some(Rational::from(-3)));
+  assert_eq!(trefoil.eval_at_root_of_unity(3, 1).to_rational(),
+             Some(Rational::from(1)));
+  assert_eq!(trefoil.eval_at_root_of_unity(3, -1), trefoil
+             .eval_at_root_of_unity(3, 1).conj());
+  for &(n, k) in [(5, 1), (5, 2), (6, 1), (8, 3), (7, -4)].iter() {
+    let exact = trefoil.eval_at_root_of_unity(n, k).to_complex();
+    let approx = trefoil.eval_complex(Complex::root_of_unity(n, k)).unwrap();
+    assert!((exact - approx).abs() < 1e-12);
+  }
+  // The Hopf link, -t^(1/2) - t^(5/2), at t = -1 with t^(1/2) = i.
+  let hopf: FracPoly = FracPoly::new(parse("-t - t^5"), 2);
+  let value = hopf.eval_at_root_of_unity(2, 1);
+  assert_eq!(value.order(), 4);
+  assert_eq!(value.to_string(), "-2*z");
+}
+
+#[test]
+#[should_panic(expected = "cyclotomic field of order zero")]
+fn root_of_unity_of_order_zero_panics() {
+  let _ = parse("t + t^3 - t^4").eval_at_root_of_unity(0, 1);
+}
+
+#[test]
+fn symmetries() {
+  let figure_eight = parse("t^-2 - t^-1 + 1 - t + t^2");
+  assert!(figure_eight.is_palindromic());
+  assert!(!figure_eight.is_antipalindromic());
+  assert_eq!(figure_eight.mirror_unit(), Some((1, 0)));
+  assert_eq!(figure_eight.symmetric_center(), Some(Rational::from(0)));
+  let trefoil = parse("t + t^3 - t^4");
+  assert!(!trefoil.is_palindromic());
+  assert_eq!(trefoil.mirror_unit(), None);
+  assert_eq!(trefoil.symmetric_center(), None);
+  let anti = parse("t - t^4");
+  assert!(anti.is_antipalindromic());
+  assert_eq!(anti.mirror_unit(), Some((-1, 5)));
+  assert_eq!(anti.mirror_unit().map(|(u, k)| {
+    let mut unit = Poly::monomial(u, k);
+    unit *= &anti.mirror();
+    unit
+  }), Some(anti.clone()));
+  assert_eq!(anti.symmetric_center(),
+             Some(Rational::new(BigInt::from(5), BigInt::from(2))));
+  assert!(Poly::<i64>::zero().is_palindromic());
+  assert!(Poly::<i64>::zero().is_antipalindromic());
+  assert_eq!(Poly::<i64>::zero().mirror_unit(), None);
+  assert_eq!(parse("3t^-7").mirror_unit(), Some((1, -14)));
+}
+
+// Number of random cases for each property.
+const PROPERTY_CASES: usize = 2000;
+
+#[test]
+fn random_generator() {
+  let mut rng = PolyRng::new(42);
+  let first: Vec<u64> = (0..3).map(|_| rng.next_u64()).collect();
+  assert_eq!(first, (0..3).map({
+    let mut again = PolyRng::new(42);
+    move |_| again.next_u64()
+  }).collect::<Vec<_>>());
+  assert_ne!(first[0], first[1]);
+  let values: BTreeSet<i64> = (0..1000).map(|_| rng.range(-3, 3)).collect();
+  assert_eq!(values, (-3..4).collect());
+  let poly: Poly = rng.poly(-5, 10, 7);
+  assert!(poly.min_degree().unwrap() >= -5 && poly.max_degree().unwrap() <= 4);
+  assert!(poly.terms().all(|(_, c)| c.abs() <= 7));
+  assert_eq!(rng.poly::<i64>(3, 0, 7), Poly::zero());
+}
+
+#[test]
+fn ring_axioms() {
+  let mut rng = PolyRng::new(1);
+  let one = Poly::number(1);
+  for _ in 0..PROPERTY_CASES {
+    let a: Poly = rng.any_poly(12, 100);
+    let b: Poly = rng.any_poly(12, 100);
+    let c: Poly = rng.any_poly(12, 100);
+    assert_eq!(&a + &b, &b + &a);
+    assert_eq!(&a * &b, &b * &a);
+    assert_eq!(&(&a + &b) + &c, &a + &(&b + &c));
+    assert_eq!(&(&a * &b) * &c, &a * &(&b * &c));
+    assert_eq!(&a * &(&b + &c), &(&a * &b) + &(&a * &c));
+    assert_eq!(&(&a - &b) + &b, a);
+    assert_eq!(&a + &Poly::zero(), a);
+    assert_eq!(&a * &one, a);
+    assert_eq!(&a + &-&a, Poly::zero());
+    assert_eq!(a.checked_mul(&b), Ok(&a * &b));
+  }
+}
+
+#[test]
+fn mixed_variable_constants() {
+  let mut rng = PolyRng::new(6);
+  let vars = ['t', 'A', 'q'];
+  for _ in 0..PROPERTY_CASES {
+    let a: Poly = rng.any_poly(6, 100).with_var('A');
+    let c = Poly::number(rng.range(-3, 3))
+      .with_var(vars[rng.range(0, 2) as usize]);
+    let d = Poly::number(rng.range(-3, 3))
+      .with_var(vars[rng.range(0, 2) as usize]);
+    for &(x, y) in [(&a, &c), (&c, &a), (&c, &d)].iter() {
+      assert_eq!(x + y, y + x);
+      assert_eq!(x * y, y * x);
+      let sums: HashSet<Poly> = vec![x + y, y + x].into_iter().collect();
+      assert_eq!(sums.len(), 1);
+      assert_eq!((x * y).cmp(&(y * x)), std::cmp::Ordering::Equal);
+      assert_eq!(x.checked_add(y), Ok(x + y));
+      assert_eq!(x.checked_sub(y), Ok(x - y));
+      assert_eq!(x.checked_mul(y), Ok(x * y));
+      assert_eq!((x + y).var(), (y + x).var());
+    }
+    if !a.is_constant() {
+      assert_eq!((&a + &c).var(), 'A');
+      assert_eq!((&c * &a).var(), 'A');
+    }
+    assert_eq!(&a - &a, Poly::zero());
+    assert_eq!(&c - &c, Poly::zero());
+    let zeros: HashSet<Poly> = vec![&a - &a, &c - &c, Poly::zero()]
+      .into_iter().collect();
+    assert_eq!(zeros.len(), 1);
+  }
+  let a = parse("A^2 - A^-1");
+  let singular = PolyMatrix::from_rows(vec![vec![a.clone(), a.clone()],
+                                            vec![a.clone(), a]]);
+  assert_eq!(singular.determinant(), Poly::zero());
+  assert_eq!(Poly::number(5).with_var('A'), Poly::<i64>::number(5));
+  assert_ne!(parse("A^2"), parse("t^2"));
+}
+
+#[test]
+fn mirror_properties() {
+  let mut rng = PolyRng::new(2);
+  for _ in 0..PROPERTY_CASES {
+    let a: Poly = rng.any_poly(12, 100);
+    let b: Poly = rng.any_poly(12, 100);
+    assert_eq!(a.mirror().mirror(), a);
+    assert_eq!((&a + &b).mirror(), &a.mirror() + &b.mirror());
+    assert_eq!((&a * &b).mirror(), &a.mirror() * &b.mirror());
+    assert_eq!(a.mirror(), a.substitute_power(-1));
+    assert_eq!(a.mirror().min_degree(), a.max_degree().map(|k| -k));
+  }
+}
+
+#[test]
+fn parse_print_round_trip() {
+  let mut rng = PolyRng::new(3);
+  for _ in 0..PROPERTY_CASES {
+    let a: Poly = rng.any_poly(12, 1000);
+    let big: Poly<BigInt> = rng.any_poly(12, 1000);
+    let frac = FracPoly::new(a.clone(), rng.range(1, 6));
+    for &notation in [Notation::Plain, Notation::Latex, Notation::Mathematica,
+                      Notation::Sage, Notation::KnotInfo].iter() {
+      assert_eq!(parse(&a.display(notation).to_string()), a);
+      assert_eq!(big.display(notation).to_string().parse(), Ok(big.clone()));
+      assert_eq!(frac.display(notation).to_string().parse(), Ok(frac.clone()));
+    }
+  }
+}
+
+#[test]
+fn random_fast_multiplication() {
+  let mut rng = PolyRng::new(4);
+  for _ in 0..PROPERTY_CASES {
+    let a: Poly = rng.any_poly(150, 1000);
+    let b: Poly = rng.any_poly(150, 1000);
+    assert_eq!(&a * &b, a.mul_naive(&b));
+  }
+  for _ in 0..PROPERTY_CASES / 10 {
+    let a: Poly<BigInt> = rng.any_poly(150, 1 << 40);
+    let b: Poly<BigInt> = rng.any_poly(150, 1 << 40);
+    assert_eq!(&a * &b, a.mul_naive(&b));
+  }
+}
+
+// Encodes the polynomial into a vector of bytes.
+fn encode<R: IntegerRing>(poly: &Poly<R>) -> Vec<u8> {
+  let mut bytes = Vec::new();
+  poly.write_to(&mut bytes).unwrap();
+  bytes
+}
+
+#[test]
+fn binary_encoding() {
+  // Version, variable, zigzag -1, two coefficients, zigzag 1 and -65.
+  let poly = parse("t^-1 - 65");
+  assert_eq!(encode(&poly), vec![1, b't', 1, 2, 2, 0x81, 0x01]);
+  assert_eq!(Poly::read_from(&mut &encode(&poly)[..]).ok(), Some(poly));
+  assert_eq!(encode(&Poly::<i64>::zero()), vec![1, b't', 0, 0]);
+
+  let mut rng = PolyRng::new(5);
+  let mut stream = Vec::new();
+  let mut polys = Vec::new();
+  for _ in 0..PROPERTY_CASES {
+    let a: Poly = rng.any_poly(20, 1 << 40).with_var('A');
+    a.write_to(&mut stream).unwrap();
+    polys.push(a);
+  }
+  let extreme = Poly::from_coefs(i64::MIN, vec![i64::MIN, 0, i64::MAX]);
+  extreme.write_to(&mut stream).unwrap();
+  polys.push(extreme);
+  let mut reader = &stream[..];
+  for a in polys.iter() {
+    assert_eq!(&Poly::<i64>::read_from(&mut reader).unwrap(), a);
+  }
+  assert!(matches!(Poly::<i64>::read_from(&mut reader),
+                   Err(DecodePolyError::EndOfInput)));
+
+  // Coefficients of any size, read back into a large enough ring.
+  let mut huge = BigInt::from(-3);
+  for _ in 0..10 {
+    huge.mul_in_place(&BigInt::from(i64::MAX));
+  }
+  let big = Poly::from_coefs(-2, vec![huge.clone(), BigInt::from(7), huge]);
+  let bytes = encode(&big);
+  assert_eq!(Poly::read_from(&mut &bytes[..]).ok(), Some(big));
+  assert!(matches!(Poly::<i64>::read_from(&mut &bytes[..]),
+                   Err(DecodePolyError::CoefOverflow)));
+  let wide = Poly::number(i128::MIN);
+  assert_eq!(Poly::read_from(&mut &encode(&wide)[..]).ok(), Some(wide));
+}
+
+#[test]
+fn corrupt_encoding() {
+  fn decode(bytes: &[u8]) -> Result<Poly, DecodePolyError> {
+    Poly::read_from(&mut &bytes[..])
+  }
+  let bytes = encode(&parse("3t^-1 - 200t^5"));
+  for len in 1..bytes.len() {
+    assert!(matches!(decode(&bytes[..len]), Err(DecodePolyError::Truncated)));
+  }
+  assert!(matches!(decode(&[]), Err(DecodePolyError::EndOfInput)));
+  assert!(matches!(decode(&[2, b't', 0, 0]),
+                   Err(DecodePolyError::UnsupportedVersion(2))));
+  assert!(matches!(decode(&[1, b'1', 0, 0]),
+                   Err(DecodePolyError::InvalidVariable(b'1'))));
+  assert!(matches!(decode(&[1, b't', 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff,
+                            0xff, 0xff, 0x02, 0]),
+                   Err(DecodePolyError::VarintOverflow)));
+  assert!(matches!(decode(&[1, b't', 0, 2, 0, 2]),
+                   Err(DecodePolyError::NotNormalized)));
+  assert!(matches!(decode(&[1, b't', 2, 0]),
+                   Err(DecodePolyError::NotNormalized)));
+  // Lowest exponent i64::MAX with two coefficients.
+  assert!(matches!(decode(&[1, b't', 0xfe, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff,
+                            0xff, 0xff, 0x01, 2, 2, 2]),
+                   Err(DecodePolyError::ExpOverflow)));
+  // Endless coefficients stop at the first group out of range of `i64`, and
+  // at the length limit for `BigInt`.
+  let mut endless = vec![1, b't', 0, 1];
+  endless.extend(std::iter::repeat_n(0xff, 1 << 20));
+  let mut reader = &endless[..];
+  assert!(matches!(Poly::<i64>::read_from(&mut reader),
+                   Err(DecodePolyError::CoefOverflow)));
+  assert_eq!(endless.len() - reader.len(), 14);
+  assert!(matches!(Poly::<BigInt>::read_from(&mut &endless[..]),
+                   Err(DecodePolyError::VarintOverflow)));
+  let mut huge = BigInt::one();
+  for _ in 0..28672 / 63 + 1 {
+    huge.mul_in_place(&BigInt::from(i64::MAX));
+  }
+  let err = Poly::number(huge).write_to(&mut Vec::new()).unwrap_err();
+  assert_eq!(err.kind(), std::io::ErrorKind::InvalidInput);
+
+  struct Failing;
+  impl std::io::Read for Failing {
+    fn read(&mut self, _: &mut [u8]) -> std::io::Result<usize> {
+      Err(std::io::Error::other("disk on fire"))
+    }
+  }
+  let err = Poly::<i64>::read_from(&mut Failing).unwrap_err();
+  assert_eq!(err.to_string(), "read failed: disk on fire");
+}
+
+#[test]
+fn power_series() {
+  let x = Series::from_poly(&parse("t^-1 + 2 + 3t + 4t^2"), 2);
+  assert_eq!(x.to_poly(), parse("t^-1 + 2 + 3t"));
+  assert_eq!((x.prec(), x.valuation()), (2, Some(-1)));
+  assert_eq!(x.get_coef(1), Some(3));
+  assert_eq!(x.get_coef(2), None);
+  assert_eq!(x.to_string(),
+             "P(t) = 1 * t^-1  +  2 * t^0  +  3 * t^1  +  O(t^2)");
+  assert_eq!(Series::<i64>::zero(3).to_string(), "P(t) = O(t^3)");
+  assert_eq!(x.truncate(0), Series::from_poly(&parse("t^-1"), 0));
+  assert_eq!(x.truncate(5), x);
+
+  // Sums keep the lower precision, products the one both factors support.
+  let y = Series::from_poly(&parse("t^2 - t^3"), 4);
+  assert_eq!(&x + &y, Series::from_poly(&parse("t^-1 + 2 + 3t"), 2));
+  assert_eq!(&x - &x, Series::zero(2));
+  assert_eq!(-&y, Series::from_poly(&parse("-t^2 + t^3"), 4));
+  // (t^-1 + 2 + 3t + O(t^2)) (t^2 - t^3 + O(t^4)) = t + t^2 + O(t^3).
+  assert_eq!(&x * &y, Series::from_poly(&parse("t + t^2"), 3));
+  assert_eq!(&y * &x, &x * &y);
+  assert_eq!(&Series::zero(3) * &y, Series::<i64>::zero(5));
+
+  // 1 / (1 - t) = 1 + t + t^2 + ...
+  let geometric = Series::from_poly(&parse("1 - t"), 6).inverse().unwrap();
+  assert_eq!(geometric,
+             Series::from_poly(&parse("1 + t + t^2 + t^3 + t^4 + t^5"), 6));
+  assert_eq!(&geometric * &Series::from_poly(&parse("1 - t"), 6),
+             Series::from_poly(&parse("1"), 6));
+  // Inversion moves the valuation from 2 to -2.
+  let shifted = Series::from_poly(&parse("-t^2 + t^3"), 5).inverse();
+  assert_eq!(shifted, Ok(Series::from_poly(&parse("-t^-2 - t^-1 - 1"), 1)));
+  assert_eq!(Series::from_poly(&parse("2 + t"), 3).inverse(),
+             Err(PolyError::NotInvertible));
+  assert_eq!(Series::<i64>::zero(3).inverse(), Err(PolyError::DivisionByZero));
+}
+
+#[test]
+fn stable_jones_tails() {
+  // Normalized Jones polynomials t^-k V(t) of the torus knots T(2, 2k + 1)
+  // agree with the series 1 + t^2 / (1 + t) in more and more coefficients.
+  let tail = &Series::from_poly(&parse("t^2"), 20)
+    * &Series::from_poly(&parse("1 + t"), 20).inverse().unwrap();
+  let tail = &Series::from_poly(&parse("1"), 20) + &tail;
+  let jones = [parse("1 + t^2 - t^3"),
+               parse("1 + t^2 - t^3 + t^4 - t^5"),
+               parse("1 + t^2 - t^3 + t^4 - t^5 + t^6 - t^7")];
+  for (k, v) in jones.iter().enumerate() {
+    let stable = 2 * k as i64 + 4;
+    assert_eq!(Series::from_poly(v, stable), tail.truncate(stable));
+    assert_ne!(Series::from_poly(v, stable + 1), tail.truncate(stable + 1));
+  }
+}